@@ -20,7 +20,13 @@ pub struct CommandBindings {
     pub channel_playlists: HashMap<KeyCode, HashMap<u8, String>>,
     pub watchhistory: HashMap<KeyCode, HashMap<u8, String>>,
     pub feed: HashMap<KeyCode, HashMap<u8, String>>,
+    pub timeline: HashMap<KeyCode, HashMap<u8, String>>,
     pub libray: HashMap<KeyCode, HashMap<u8, String>>,
+    pub queue: HashMap<KeyCode, HashMap<u8, String>>,
+    pub now_playing: HashMap<KeyCode, HashMap<u8, String>>,
+    pub downloads: HashMap<KeyCode, HashMap<u8, String>>,
+    pub local_playlists: HashMap<KeyCode, HashMap<u8, String>>,
+    pub local_playlist: HashMap<KeyCode, HashMap<u8, String>>,
 }
 
 impl Key for CommandBindings {
@@ -41,7 +47,8 @@ impl CommandBindings {
             Page::MainMenu(MainMenuPage::Popular) => get_command(key, &self.popular),
             Page::MainMenu(MainMenuPage::History) => get_command(key, &self.watchhistory),
             Page::MainMenu(MainMenuPage::Library) => get_command(key, &self.libray),
-            Page::Feed => get_command(key, &self.feed),
+            Page::Feed(_) => get_command(key, &self.feed),
+            Page::Timeline(_) => get_command(key, &self.timeline),
             Page::SingleItem(SingleItemPage::Video(_)) => get_command(key, &self.video),
             Page::SingleItem(SingleItemPage::Playlist(_)) => get_command(key, &self.playlist),
             Page::ChannelDisplay(ChannelDisplayPage {
@@ -56,6 +63,11 @@ impl CommandBindings {
                 r#type: ChannelDisplayPageType::Playlists,
                 ..
             }) => get_command(key, &self.channel_playlists),
+            Page::Queue => get_command(key, &self.queue),
+            Page::NowPlaying => get_command(key, &self.now_playing),
+            Page::Downloads => get_command(key, &self.downloads),
+            Page::LocalPlaylists => get_command(key, &self.local_playlists),
+            Page::LocalPlaylist(_) => get_command(key, &self.local_playlist),
         };
 
         if let Some(command) = command {
@@ -93,8 +105,20 @@ pub struct CommandBindingsSerde {
     pub watchhistory: HashMap<KeyCodeSerde, HashMap<u8, String>>,
     #[serde(default = "feed_default")]
     pub feed: HashMap<KeyCodeSerde, HashMap<u8, String>>,
+    #[serde(default = "timeline_default")]
+    pub timeline: HashMap<KeyCodeSerde, HashMap<u8, String>>,
     #[serde(default = "library_default")]
     pub library: HashMap<KeyCodeSerde, HashMap<u8, String>>,
+    #[serde(default = "queue_default")]
+    pub queue: HashMap<KeyCodeSerde, HashMap<u8, String>>,
+    #[serde(default = "now_playing_default")]
+    pub now_playing: HashMap<KeyCodeSerde, HashMap<u8, String>>,
+    #[serde(default = "downloads_default")]
+    pub downloads: HashMap<KeyCodeSerde, HashMap<u8, String>>,
+    #[serde(default = "local_playlists_default")]
+    pub local_playlists: HashMap<KeyCodeSerde, HashMap<u8, String>>,
+    #[serde(default = "local_playlist_default")]
+    pub local_playlist: HashMap<KeyCodeSerde, HashMap<u8, String>>,
 }
 
 impl ConfigTrait for CommandBindingsSerde {
@@ -115,7 +139,13 @@ impl CommandBindingsSerde {
             trending: de_serde(self.trending)?,
             watchhistory: de_serde(self.watchhistory)?,
             feed: de_serde(self.feed)?,
+            timeline: de_serde(self.timeline)?,
             libray: de_serde(self.library)?,
+            queue: de_serde(self.queue)?,
+            now_playing: de_serde(self.now_playing)?,
+            downloads: de_serde(self.downloads)?,
+            local_playlists: de_serde(self.local_playlists)?,
+            local_playlist: de_serde(self.local_playlist)?,
         })
     }
 }
@@ -134,7 +164,13 @@ impl Default for CommandBindingsSerde {
             trending: trending_default(),
             watchhistory: watchhistory_default(),
             feed: feed_default(),
+            timeline: timeline_default(),
             library: library_default(),
+            queue: queue_default(),
+            now_playing: now_playing_default(),
+            downloads: downloads_default(),
+            local_playlists: local_playlists_default(),
+            local_playlist: local_playlist_default(),
         }
     }
 }
@@ -255,7 +291,20 @@ fn trending_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
 }
 
 fn video_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
-    HashMap::new()
+    HashMap::from([
+        (
+            KeyCodeSerde::Char('/'),
+            HashMap::from([(0, String::from("prompt captions search"))]),
+        ),
+        (
+            KeyCodeSerde::Char('n'),
+            HashMap::from([(0, String::from("captions next"))]),
+        ),
+        (
+            KeyCodeSerde::Char('N'),
+            HashMap::from([(1, String::from("captions prev"))]),
+        ),
+    ])
 }
 
 fn watchhistory_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
@@ -281,6 +330,46 @@ fn feed_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
             KeyCodeSerde::Char('p'),
             HashMap::from([(2, String::from("parrun mpv '${hover-video-url}'"))]),
         ),
+        (
+            KeyCodeSerde::Char('g'),
+            HashMap::from([(0, String::from("prompt group add ${hover-channel-id} "))]),
+        ),
+        (
+            KeyCodeSerde::Char('G'),
+            HashMap::from([(1, String::from("prompt group remove ${hover-channel-id} "))]),
+        ),
+        (
+            KeyCodeSerde::Char('t'),
+            HashMap::from([(0, String::from("loadpage timeline"))]),
+        ),
+        (
+            KeyCodeSerde::Char('x'),
+            HashMap::from([(0, String::from("prompt filter add ${hover-channel-id} "))]),
+        ),
+    ])
+}
+
+fn timeline_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
+    HashMap::from([
+        (
+            KeyCodeSerde::Char('a'),
+            HashMap::from([(
+                2,
+                String::from("parrun ${terminal-emulator} mpv '${hover-url}' --no-video"),
+            )]),
+        ),
+        (
+            KeyCodeSerde::Char('p'),
+            HashMap::from([(2, String::from("parrun mpv '${hover-url}'"))]),
+        ),
+        (
+            KeyCodeSerde::Char('m'),
+            HashMap::from([(0, String::from("subscriptions markseen"))]),
+        ),
+        (
+            KeyCodeSerde::Char('x'),
+            HashMap::from([(0, String::from("prompt filter add ${hover-id} "))]),
+        ),
     ])
 }
 
@@ -291,3 +380,143 @@ fn library_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
         (KeyCodeSerde::Char('p'), HashMap::from([(2, String::from("parrun mpv '${hover-url}'"))])),
     ])
 }
+
+fn queue_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
+    HashMap::from([
+        (
+            KeyCodeSerde::Char('x'),
+            HashMap::from([(0, String::from("queue remove ${hover-id} ;; reload"))]),
+        ),
+        (
+            KeyCodeSerde::Char('s'),
+            HashMap::from([(0, String::from("queue shuffle ;; reload"))]),
+        ),
+        (
+            KeyCodeSerde::Char('p'),
+            HashMap::from([(2, String::from("queue play ${hover-id}"))]),
+        ),
+    ])
+}
+
+fn now_playing_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
+    HashMap::from([
+        (
+            KeyCodeSerde::Char(','),
+            HashMap::from([(0, String::from("mpv seek -5"))]),
+        ),
+        (
+            KeyCodeSerde::Char('.'),
+            HashMap::from([(0, String::from("mpv seek 5"))]),
+        ),
+        (
+            KeyCodeSerde::Char('<'),
+            HashMap::from([(1, String::from("mpv seek -60"))]),
+        ),
+        (
+            KeyCodeSerde::Char('>'),
+            HashMap::from([(1, String::from("mpv seek 60"))]),
+        ),
+        (
+            KeyCodeSerde::Char('9'),
+            HashMap::from([(0, String::from("mpv add volume -5"))]),
+        ),
+        (
+            KeyCodeSerde::Char('0'),
+            HashMap::from([(0, String::from("mpv add volume 5"))]),
+        ),
+        (
+            KeyCodeSerde::Char('['),
+            HashMap::from([(0, String::from("mpv multiply speed 0.9"))]),
+        ),
+        (
+            KeyCodeSerde::Char(']'),
+            HashMap::from([(0, String::from("mpv multiply speed 1.1"))]),
+        ),
+        (
+            KeyCodeSerde::Char('='),
+            HashMap::from([(0, String::from("mpv sprop speed 1"))]),
+        ),
+        (
+            KeyCodeSerde::Char('L'),
+            HashMap::from([(1, String::from("mpv cycle-values loop-file inf no"))]),
+        ),
+    ])
+}
+
+fn downloads_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
+    HashMap::from([
+        (
+            KeyCodeSerde::Char('x'),
+            HashMap::from([(0, String::from("download remove ${hover-job}"))]),
+        ),
+        (
+            KeyCodeSerde::Char('p'),
+            HashMap::from([(0, String::from("download pause ${hover-job}"))]),
+        ),
+        (
+            KeyCodeSerde::Char('r'),
+            HashMap::from([(0, String::from("download resume ${hover-job}"))]),
+        ),
+        (
+            KeyCodeSerde::Char('c'),
+            HashMap::from([(0, String::from("download cancel ${hover-job}"))]),
+        ),
+        (
+            KeyCodeSerde::Char('C'),
+            HashMap::from([(1, String::from("download clear"))]),
+        ),
+    ])
+}
+
+fn local_playlists_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
+    HashMap::from([
+        (
+            KeyCodeSerde::Char('p'),
+            HashMap::from([(2, String::from("playlist play ${hover-playlist}"))]),
+        ),
+        (
+            KeyCodeSerde::Char('e'),
+            HashMap::from([(0, String::from("playlist export ${hover-playlist}"))]),
+        ),
+        (
+            KeyCodeSerde::Char('X'),
+            HashMap::from([(
+                1,
+                String::from("playlist delete ${hover-playlist} ;; reload"),
+            )]),
+        ),
+    ])
+}
+
+fn local_playlist_default() -> HashMap<KeyCodeSerde, HashMap<u8, String>> {
+    HashMap::from([
+        (
+            KeyCodeSerde::Char('x'),
+            HashMap::from([(
+                0,
+                String::from("playlist remove ${playlist-name} ${hover-id} ;; reload"),
+            )]),
+        ),
+        (
+            KeyCodeSerde::Char('K'),
+            HashMap::from([(
+                1,
+                String::from("playlist move ${playlist-name} ${hover-id} up ;; reload"),
+            )]),
+        ),
+        (
+            KeyCodeSerde::Char('J'),
+            HashMap::from([(
+                1,
+                String::from("playlist move ${playlist-name} ${hover-id} down ;; reload"),
+            )]),
+        ),
+        (
+            KeyCodeSerde::Char('p'),
+            HashMap::from([(
+                2,
+                String::from("playlist play ${playlist-name} ${hover-id}"),
+            )]),
+        ),
+    ])
+}
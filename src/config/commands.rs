@@ -90,14 +90,19 @@ fn launch_command_default() -> String {
 
 fn video_default() -> Vec<HashMap<String, String>> {
     vec![
+        HashMap::from([(String::from("Switch view"), String::from("%switch-view%"))]),
         HashMap::from([(
             String::from("Reload updated video"),
-            String::from("run rm '~/.cache/youtube-tui/info/${id}.json' ;; video ${id}"),
+            String::from("cache clear video ${id} ;; video ${id}"),
         )]),
         HashMap::from([(
             String::from("Play video"),
             String::from("parrun ${video-player} '${embed-url}'"),
         )]),
+        HashMap::from([(
+            String::from("Play video from chapter"),
+            String::from("parrun ${video-player} '${embed-url}' --start=${start-time}"),
+        )]),
         HashMap::from([(
             String::from("Play audio"),
             String::from("mpv stop ;; resume ;; mpv sprop loop-file no ;; mpv loadfile '${embed-url}' ;; echo mpv Player started"),
@@ -106,10 +111,14 @@ fn video_default() -> Vec<HashMap<String, String>> {
             String::from("Play audio (loop)"),
             String::from("mpv stop ;; resume ;; mpv sprop loop-file inf ;; mpv loadfile '${embed-url}' ;; echo mpv Player started"),
         )]),
-        // HashMap::from([(
-        //     String::from("Add to queue"),
-        //     String::from("mpv loadfile '${embed-url}' appendplay;; echo mpv Added to queue"),
-        // )]),
+        HashMap::from([(
+            String::from("Play audio from chapter"),
+            String::from("mpv chapter ${chapter}"),
+        )]),
+        HashMap::from([(
+            String::from("Add to queue"),
+            String::from("queue add ${id}"),
+        )]),
         HashMap::from([(
             String::from("View channel"),
             String::from("channel ${channel-id}"),
@@ -128,11 +137,11 @@ fn video_default() -> Vec<HashMap<String, String>> {
         )]),
         HashMap::from([(
             String::from("Save video to library"),
-            String::from("bookmark ${id} ;; run rm -rf '${save-path}${id}.*' ;; parrun ${terminal-emulator} ${youtube-downloader} '${embed-url}' -o '${save-path}%(title)s[%(id)s].%(ext)s'")
+            String::from("download ${id}")
         )]),
         HashMap::from([(
             String::from("Save audio to library"),
-            String::from("bookmark ${id} ;; parrun rm -rf '${save-path}${id}.*' ;; parrun ${terminal-emulator} ${youtube-downloader} '${embed-url}' -x -o '${save-path}%(title)s[%(id)s].%(ext)s'")
+            String::from("download audio ${id}")
         )]),
         HashMap::from([(
             String::from("Mode: ${provider}"),
@@ -143,9 +152,10 @@ fn video_default() -> Vec<HashMap<String, String>> {
 
 fn saved_video_default() -> Vec<HashMap<String, String>> {
     vec![
+        HashMap::from([(String::from("Switch view"), String::from("%switch-view%"))]),
         HashMap::from([(
             String::from("Reload updated video"),
-            String::from("run rm '~/.cache/youtube-tui/info/${id}.json' ;; video ${id}"),
+            String::from("cache clear video ${id} ;; video ${id}"),
         )]),
         HashMap::from([(
             String::from("[Offline] Play saved file"),
@@ -159,10 +169,14 @@ fn saved_video_default() -> Vec<HashMap<String, String>> {
             String::from("[Offline] Play saved file (audio loop)"),
             String::from("mpv stop ;; resume ;; mpv sprop loop-file inf ;; mpv loadfile '${offline-path}' ;; echo mpv Player started"),
         )]),
-        // HashMap::from([(
-        //     String::from("[Offline] Add to queue"),
-        //     String::from("mpv loadfile '${offline-path}' appendplay ;; echo mpv Added to queue"),
-        // )]),
+        HashMap::from([(
+            String::from("Play audio from chapter"),
+            String::from("mpv chapter ${chapter}"),
+        )]),
+        HashMap::from([(
+            String::from("[Offline] Add to queue"),
+            String::from("queue add ${id}"),
+        )]),
         HashMap::from([(
             String::from("View channel"),
             String::from("channel ${channel-id}"),
@@ -181,15 +195,15 @@ fn saved_video_default() -> Vec<HashMap<String, String>> {
         )]),
         HashMap::from([(
             String::from("Redownload video to library"),
-            String::from("bookmark ${id} ;; run rm ${save-path}*${id}*.* ;; parrun ${terminal-emulator} ${youtube-downloader} ${embed-url} -o '${save-path}%(title)s[%(id)s].%(ext)s'"),
+            String::from("library delete ${id} ;; download ${id}"),
         )]),
         HashMap::from([(
             String::from("Redownload audio to library"),
-            String::from("bookmark ${id} ;; run rm ${save-path}*${id}*.* ;; parrun ${terminal-emulator} ${youtube-downloader} ${embed-url} -x -o '${save-path}%(title)s[%(id)s].%(ext)s'")
+            String::from("library delete ${id} ;; download audio ${id}")
         )]),
         HashMap::from([(
             String::from("Delete saved file"),
-            String::from("run rm ${save-path}*${id}*.*")
+            String::from("library delete ${id}")
         )]),
     ]
 }
@@ -199,7 +213,7 @@ fn playlist_default() -> Vec<HashMap<String, String>> {
         HashMap::from([(String::from("Switch view"), String::from("%switch-view%"))]),
         HashMap::from([(
             String::from("Reload updated playlist"),
-            String::from("run rm ~/.cache/youtube-tui/info/${id}.json ;; reload"),
+            String::from("cache clear playlist ${id} ;; reload"),
         )]),
         HashMap::from([(
             String::from("Play all (videos)"),
@@ -213,10 +227,10 @@ fn playlist_default() -> Vec<HashMap<String, String>> {
             String::from("Shuffle play all (audio loop)"),
             String::from("mpv stop ;; resume ;; ${mpv-queuelist} ;; mpv sprop loop-playlist yes ;; mpv playlist-shuffle ;; mpv playlist-play-index 0 ;; echo mpv Player started"),
         )]),
-        // HashMap::from([(
-        //     String::from("Add all to queue"),
-        //     String::from("${mpv-queuelist} ;; echo mpv Queued playlist"),
-        // )]),
+        HashMap::from([(
+            String::from("Add all to queue"),
+            String::from("queue add ${id}"),
+        )]),
         HashMap::from([(
             String::from("View channel"),
             String::from("channel ${channel-id}"),
@@ -235,11 +249,11 @@ fn playlist_default() -> Vec<HashMap<String, String>> {
         )]),
         HashMap::from([(
             String::from("Save playlist videos to library"),
-            String::from("bookmark ${id} ;; run rm -rf '${save-path}*${id}*' ;; parrun ${terminal-emulator} bash -c \"${youtube-downloader} ${all-videos} -o '\"'${save-path}${title}[${id}]/%(title)s[%(id)s].%(ext)s'\"'\"")
+            String::from("download ${id}")
         )]),
         HashMap::from([(
             String::from("Save playlist audio to library"),
-            String::from("bookmark ${id} ;; run rm -rf '${save-path}*${id}*' ;; parrun ${terminal-emulator} bash -c \"${youtube-downloader} ${all-videos} -x -o '\"'${save-path}${title}[${id}]/%(title)s[%(id)s].%(ext)s'\"'\"")
+            String::from("download audio ${id}")
         )]),
         HashMap::from([(
             String::from("Mode: ${provider}"),
@@ -253,11 +267,11 @@ fn saved_playlist_default() -> Vec<HashMap<String, String>> {
         HashMap::from([(String::from("Switch view"), String::from("%switch-view%"))]),
         HashMap::from([(
             String::from("Reload updated playlist"),
-            String::from("run rm ~/.cache/youtube-tui/info/${id}.json ;; reload"),
+            String::from("cache clear playlist ${id} ;; reload"),
         )]),
         HashMap::from([(
             String::from("[Offline] Play all (videos)"),
-            String::from("parrun ${video-player} ${save-path}*${id}*/* --force-window"),
+            String::from("parrun ${video-player} '${offline-path}' --force-window"),
         )]),
         HashMap::from([(
             String::from("[Offline] Play all (audio)"),
@@ -289,15 +303,15 @@ fn saved_playlist_default() -> Vec<HashMap<String, String>> {
         )]),
         HashMap::from([(
             String::from("Redownload playlist videos to library"),
-            String::from("bookmark ${id} ;; run rm -rf ${save-path}*${id}* ;; parrun ${terminal-emulator} bash -c \"${youtube-downloader} ${all-videos} -o '\"'${save-path}${title}[${id}]/%(title)s[%(id)s].%(ext)s'\"'\"")
+            String::from("library delete ${id} ;; download ${id}")
         )]),
         HashMap::from([(
             String::from("Redownload playlist audio to library"),
-            String::from("bookmark ${id} ;; run rm -rf ${save-path}*${id}* ;; parrun ${terminal-emulator} bash -c \"${youtube-downloader} ${all-videos} -x -o '\"'${save-path}${title}[${id}]/%(title)s[%(id)s].%(ext)s'\"'\"")
+            String::from("library delete ${id} ;; download audio ${id}")
         )]),
         HashMap::from([(
             String::from("Delete saved files"),
-            String::from("run rm -rf ${save-path}*${id}*")
+            String::from("library delete ${id}")
         )]),
     ]
 }
@@ -29,8 +29,13 @@ impl Default for CommandsRemapConfig {
                 "loadpage watchhistory".to_string(),
             ),
             ("feed".to_string(), "loadpage feed".to_string()),
+            ("timeline".to_string(), "loadpage timeline".to_string()),
             ("bookmarks".to_string(), "loadpage bookmarks".to_string()),
             ("library".to_string(), "loadpage library".to_string()),
+            ("queue".to_string(), "loadpage queue".to_string()),
+            ("downloads".to_string(), "loadpage downloads".to_string()),
+            ("playlists".to_string(), "loadpage playlists".to_string()),
+            ("nowplaying".to_string(), "loadpage nowplaying".to_string()),
             ("search".to_string(), "loadpage search".to_string()),
             ("channel".to_string(), "loadpage channel".to_string()),
             ("video".to_string(), "loadpage video".to_string()),
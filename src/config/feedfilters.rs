@@ -0,0 +1,146 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use typemap::Key;
+
+use crate::global::{structs::MiniVideoItem, traits::ConfigTrait};
+
+/// Rules compiled from `FeedFiltersConfig`, decides which videos are hidden from the feed
+#[derive(Clone, Default)]
+pub struct FeedFilters {
+    pub rules: Vec<FeedFilter>,
+    pub shorts_max_length: u32,
+}
+
+/// A single compiled rule, a video is hidden if it matches every condition of the rule
+#[derive(Clone)]
+pub struct FeedFilter {
+    pub channel: Option<String>,
+    pub title: Option<Regex>,
+    pub min_length: Option<u32>,
+    pub max_length: Option<u32>,
+    pub shorts: bool,
+}
+
+impl Key for FeedFilters {
+    type Value = Self;
+}
+
+impl TryFrom<FeedFiltersConfig> for FeedFilters {
+    type Error = regex::Error;
+
+    fn try_from(original: FeedFiltersConfig) -> Result<Self, Self::Error> {
+        Ok(Self {
+            rules: original
+                .rules
+                .into_iter()
+                .map(|rule| {
+                    Ok(FeedFilter {
+                        channel: rule.channel,
+                        title: rule.title.as_deref().map(Regex::new).transpose()?,
+                        min_length: rule.min_length,
+                        max_length: rule.max_length,
+                        shorts: rule.shorts,
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+            shorts_max_length: original.shorts_max_length,
+        })
+    }
+}
+
+impl FeedFilters {
+    /// returns true if any rule matches the video
+    pub fn hides(&self, video: &MiniVideoItem) -> bool {
+        self.rules
+            .iter()
+            .any(|rule| rule.matches(video, self.shorts_max_length))
+    }
+
+    /// remove hidden videos
+    pub fn apply(&self, videos: Vec<MiniVideoItem>) -> Vec<MiniVideoItem> {
+        videos
+            .into_iter()
+            .filter(|video| !self.hides(video))
+            .collect()
+    }
+}
+
+impl FeedFilter {
+    fn matches(&self, video: &MiniVideoItem, shorts_max_length: u32) -> bool {
+        // videos with unknown lengths (e.g. synced from Atom feeds) never match length conditions
+        let length = length_secs(&video.length);
+        let conditions = [
+            self.channel
+                .as_ref()
+                .map(|channel| *channel == video.channel_id),
+            self.title
+                .as_ref()
+                .map(|title| title.is_match(&video.title)),
+            self.min_length
+                .map(|min| length.is_some_and(|length| length < min)),
+            self.max_length
+                .map(|max| length.is_some_and(|length| length > max)),
+            self.shorts
+                .then(|| length.is_some_and(|length| length <= shorts_max_length)),
+        ];
+
+        // a rule without conditions does nothing, instead of hiding everything
+        conditions.iter().any(Option::is_some)
+            && conditions.iter().flatten().all(|matched| *matched)
+    }
+}
+
+/// Rules for hiding videos from the subscription feed, videos hidden by a rule are not saved
+/// when syncing, and are not shown in the timeline
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FeedFiltersConfig {
+    /// videos this long or shorter are shorts (in seconds)
+    #[serde(default = "shorts_max_length_default")]
+    pub shorts_max_length: u32,
+    #[serde(default)]
+    pub rules: Vec<FeedFilterRule>,
+}
+
+impl ConfigTrait for FeedFiltersConfig {
+    const LABEL: &'static str = "feedfilters";
+}
+
+impl Default for FeedFiltersConfig {
+    fn default() -> Self {
+        Self {
+            shorts_max_length: shorts_max_length_default(),
+            rules: Vec::new(),
+        }
+    }
+}
+
+/// Conditions of a rule, conditions left out match all videos
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct FeedFilterRule {
+    /// id of the channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    /// regex matched against the video title
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// hide videos shorter than this (in seconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<u32>,
+    /// hide videos longer than this (in seconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<u32>,
+    /// hide shorts
+    #[serde(default)]
+    pub shorts: bool,
+}
+
+const fn shorts_max_length_default() -> u32 {
+    60
+}
+
+/// reverse of `secs_display_string`, turns `1:02:43` into seconds
+fn length_secs(length: &str) -> Option<u32> {
+    length.split(':').try_fold(0, |secs: u32, part| {
+        Some(secs * 60 + part.parse::<u32>().ok()?)
+    })
+}
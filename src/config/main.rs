@@ -24,6 +24,12 @@ pub struct MainConfig {
     pub syncing: SyncConfig,
     #[serde(default)]
     pub limits: Limits,
+    #[serde(default)]
+    pub downloads: DownloadsConfig,
+    #[serde(default)]
+    pub instances: InstancesConfig,
+    #[serde(default)]
+    pub cache: CacheConfig,
     #[serde(default = "image_index_default")]
     // The image to download from the array of images provided by the invidious api
     // 0 is usually `maxres` and 3 (default) is good enough for normal uses without having huge files sizes
@@ -35,6 +41,11 @@ pub struct MainConfig {
     pub shell: String,
     #[serde(default = "legacy_input_handling_default")]
     pub legacy_input_handling: bool,
+    #[serde(default = "resume_playback_default")]
+    pub resume_playback: bool,
+    /// shell command ran for each new video found when syncing subscriptions
+    #[serde(default)]
+    pub notify_command: String,
     #[serde(default = "default_env")]
     pub env: HashMap<String, String>,
 }
@@ -56,10 +67,15 @@ impl Default for MainConfig {
             refresh_after_modifying_search_filters: refresh_after_modifying_search_filters_default(
             ),
             limits: Limits::default(),
+            downloads: DownloadsConfig::default(),
+            instances: InstancesConfig::default(),
+            cache: CacheConfig::default(),
             syncing: sync_config_default(),
             provider: provider_default(),
             shell: shell_default(),
             legacy_input_handling: legacy_input_handling_default(),
+            resume_playback: resume_playback_default(),
+            notify_command: String::new(),
 
             env: default_env(),
         }
@@ -70,7 +86,7 @@ impl ConfigTrait for MainConfig {
     const LABEL: &'static str = "main";
 }
 
-#[derive(Clone, Copy, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct SyncConfig {
     #[serde(default = "download_images_default")]
     pub download_images: bool,
@@ -80,17 +96,59 @@ pub struct SyncConfig {
     pub sync_channel_cooldown_secs: u64,
     #[serde(default = "sync_videos_cooldown_secs_default")]
     pub sync_videos_cooldown_secs: u64,
+    #[serde(default = "sync_backend_default")]
+    pub backend: SyncBackend,
+    #[serde(default = "feed_url_default")]
+    pub feed_url: String,
+    /// number of channels to sync at the same time
+    #[serde(default = "sync_workers_default")]
+    pub workers: usize,
+    #[serde(default = "sync_timeout_secs_default")]
+    pub timeout_secs: u64,
+    #[serde(default = "sync_retries_default")]
+    pub retries: u32,
+    #[serde(default = "sync_retry_delay_secs_default")]
+    pub retry_delay_secs: u64,
+    /// sync all subscriptions every n seconds while the TUI is running, 0 to disable
+    #[serde(default)]
+    pub auto_sync_interval_secs: u64,
 }
 
-const fn sync_config_default() -> SyncConfig {
+impl SyncConfig {
+    /// the feed url to sync videos from, `None` if videos should be synced from invidious
+    pub fn feed_url(&self) -> Option<&str> {
+        match self.backend {
+            SyncBackend::Feed => Some(&self.feed_url),
+            SyncBackend::Invidious => None,
+        }
+    }
+}
+
+fn sync_config_default() -> SyncConfig {
     SyncConfig {
         download_images: download_images_default(),
         sync_channel_info: sync_channel_info_default(),
         sync_channel_cooldown_secs: sync_channel_cooldown_secs_default(),
         sync_videos_cooldown_secs: sync_videos_cooldown_secs_default(),
+        backend: sync_backend_default(),
+        feed_url: feed_url_default(),
+        workers: sync_workers_default(),
+        timeout_secs: sync_timeout_secs_default(),
+        retries: sync_retries_default(),
+        retry_delay_secs: sync_retry_delay_secs_default(),
+        auto_sync_interval_secs: 0,
     }
 }
 
+/// where subscription videos are synced from
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SyncBackend {
+    /// the channel videos api of the invidious instance
+    Invidious,
+    /// the channel's Atom feed, falls back to invidious if the feed cannot be fetched
+    Feed,
+}
+
 #[derive(Clone, Copy, Serialize, Deserialize)]
 pub struct Limits {
     #[serde(default = "watch_history_default")]
@@ -99,6 +157,12 @@ pub struct Limits {
     pub search_history: usize,
     #[serde(default = "commands_history_default")]
     pub commands_history: usize,
+    /// number of videos on each page of the timeline
+    #[serde(default = "timeline_page_default")]
+    pub timeline_page: usize,
+    /// number of entries shown in the search bar dropdown, 0 to disable suggestions
+    #[serde(default = "search_suggestions_default")]
+    pub search_suggestions: usize,
 }
 
 impl Default for Limits {
@@ -107,6 +171,92 @@ impl Default for Limits {
             watch_history: watch_history_default(),
             search_history: search_history_default(),
             commands_history: commands_history_default(),
+            timeline_page: timeline_page_default(),
+            search_suggestions: search_suggestions_default(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct DownloadsConfig {
+    #[serde(default = "max_concurrent_default")]
+    pub max_concurrent: usize,
+    #[serde(default = "retries_default")]
+    pub retries: u32,
+    #[serde(default = "retry_delay_secs_default")]
+    pub retry_delay_secs: u64,
+}
+
+impl Default for DownloadsConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent: max_concurrent_default(),
+            retries: retries_default(),
+            retry_delay_secs: retry_delay_secs_default(),
+        }
+    }
+}
+
+/// other invidious instances to fail over to when `invidious_instance` is down
+#[derive(Clone, Serialize, Deserialize)]
+pub struct InstancesConfig {
+    /// tried in order after `invidious_instance`
+    #[serde(default)]
+    pub fallbacks: Vec<String>,
+    /// check the health of all instances every n seconds, 0 to only check on launch
+    #[serde(default = "check_interval_secs_default")]
+    pub check_interval_secs: u64,
+    #[serde(default = "check_timeout_secs_default")]
+    pub check_timeout_secs: u64,
+    /// `instance discover` skips instances with a lower 30 day uptime (in percent)
+    #[serde(default = "discover_min_uptime_default")]
+    pub discover_min_uptime: f32,
+    /// switch to offline mode after this many pages failed to load in a row, 0 to never switch
+    #[serde(default = "offline_after_failures_default")]
+    pub offline_after_failures: u32,
+}
+
+impl Default for InstancesConfig {
+    fn default() -> Self {
+        Self {
+            fallbacks: Vec::new(),
+            check_interval_secs: check_interval_secs_default(),
+            check_timeout_secs: check_timeout_secs_default(),
+            discover_min_uptime: discover_min_uptime_default(),
+            offline_after_failures: offline_after_failures_default(),
+        }
+    }
+}
+
+/// responses from invidious kept in `~/.cache/youtube-tui/responses/`, a ttl of 0 disables
+/// caching for that kind
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct CacheConfig {
+    /// least recently used responses are removed when the cache grows larger than this (in MB)
+    #[serde(default = "max_size_mb_default")]
+    pub max_size_mb: u64,
+    #[serde(default = "videos_ttl_secs_default")]
+    pub videos_ttl_secs: u64,
+    #[serde(default = "channels_ttl_secs_default")]
+    pub channels_ttl_secs: u64,
+    #[serde(default = "playlists_ttl_secs_default")]
+    pub playlists_ttl_secs: u64,
+    #[serde(default = "search_ttl_secs_default")]
+    pub search_ttl_secs: u64,
+    /// also used for popular videos
+    #[serde(default = "trending_ttl_secs_default")]
+    pub trending_ttl_secs: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            max_size_mb: max_size_mb_default(),
+            videos_ttl_secs: videos_ttl_secs_default(),
+            channels_ttl_secs: channels_ttl_secs_default(),
+            playlists_ttl_secs: playlists_ttl_secs_default(),
+            search_ttl_secs: search_ttl_secs_default(),
+            trending_ttl_secs: trending_ttl_secs_default(),
         }
     }
 }
@@ -200,6 +350,66 @@ const fn watch_history_default() -> usize {
     50
 }
 
+const fn check_interval_secs_default() -> u64 {
+    600
+}
+
+const fn check_timeout_secs_default() -> u64 {
+    10
+}
+
+const fn discover_min_uptime_default() -> f32 {
+    95.0
+}
+
+const fn offline_after_failures_default() -> u32 {
+    3
+}
+
+const fn max_size_mb_default() -> u64 {
+    100
+}
+
+const fn videos_ttl_secs_default() -> u64 {
+    86400
+}
+
+const fn channels_ttl_secs_default() -> u64 {
+    3600
+}
+
+const fn playlists_ttl_secs_default() -> u64 {
+    3600
+}
+
+const fn search_ttl_secs_default() -> u64 {
+    1800
+}
+
+const fn trending_ttl_secs_default() -> u64 {
+    1800
+}
+
+const fn timeline_page_default() -> usize {
+    50
+}
+
+const fn search_suggestions_default() -> usize {
+    8
+}
+
+const fn max_concurrent_default() -> usize {
+    2
+}
+
+const fn retries_default() -> u32 {
+    3
+}
+
+const fn retry_delay_secs_default() -> u64 {
+    10
+}
+
 const fn mouse_support_default() -> bool {
     true
 }
@@ -248,6 +458,34 @@ const fn sync_videos_cooldown_secs_default() -> u64 {
     600
 }
 
+const fn sync_backend_default() -> SyncBackend {
+    SyncBackend::Invidious
+}
+
+fn feed_url_default() -> String {
+    String::from("https://www.youtube.com/feeds/videos.xml?channel_id=")
+}
+
+const fn sync_workers_default() -> usize {
+    8
+}
+
+const fn sync_timeout_secs_default() -> u64 {
+    30
+}
+
+const fn sync_retries_default() -> u32 {
+    2
+}
+
+const fn sync_retry_delay_secs_default() -> u64 {
+    2
+}
+
 const fn legacy_input_handling_default() -> bool {
     false
 }
+
+const fn resume_playback_default() -> bool {
+    true
+}
@@ -30,12 +30,24 @@ pub struct PagesConfig {
     pub main_menu: PageConfig,
     #[serde(default = "feed_default")]
     pub feed: PageConfig,
+    #[serde(default = "timeline_default")]
+    pub timeline: PageConfig,
     #[serde(default = "search_default")]
     pub search: PageConfig,
     #[serde(default = "singleitem_default")]
     pub singleitem: PageConfig,
     #[serde(default = "channeldisplay_default")]
     pub channeldisplay: PageConfig,
+    #[serde(default = "queue_default")]
+    pub queue: PageConfig,
+    #[serde(default = "now_playing_default")]
+    pub now_playing: PageConfig,
+    #[serde(default = "downloads_default")]
+    pub downloads: PageConfig,
+    #[serde(default = "local_playlists_default")]
+    pub local_playlists: PageConfig,
+    #[serde(default = "local_playlist_default")]
+    pub local_playlist: PageConfig,
 }
 
 impl Key for PagesConfig {
@@ -51,9 +63,15 @@ impl Default for PagesConfig {
         Self {
             main_menu: main_menu_default(),
             feed: feed_default(),
+            timeline: timeline_default(),
             search: search_default(),
             singleitem: singleitem_default(),
             channeldisplay: channeldisplay_default(),
+            queue: queue_default(),
+            now_playing: now_playing_default(),
+            downloads: downloads_default(),
+            local_playlists: local_playlists_default(),
+            local_playlist: local_playlist_default(),
         }
     }
 }
@@ -188,6 +206,12 @@ pub enum PageItems {
     ChannelVideos,
     /// button which loads the channel playlists page
     ChannelPlaylists,
+    /// the built in player with controls and chapters
+    NowPlaying,
+    /// download jobs and their progress
+    DownloadList,
+    /// local playlists made by the user
+    LocalPlaylistList,
 }
 
 impl PageItems {
@@ -210,6 +234,9 @@ impl PageItems {
             Self::ChannelMain => Box::new(PageButton::ChannelMain),
             Self::ChannelVideos => Box::new(PageButton::ChannelVideos),
             Self::ChannelPlaylists => Box::new(PageButton::ChannelPlaylists),
+            Self::NowPlaying => Box::<NowPlaying>::default(),
+            Self::DownloadList => Box::<DownloadList>::default(),
+            Self::LocalPlaylistList => Box::<LocalPlaylistList>::default(),
         }
     }
 
@@ -229,8 +256,11 @@ impl PageItems {
             | Self::SingleItemInfo
             | Self::ChannelDisplay
             | Self::ChannelList
-            | Self::VideoList => Constraint::Min(9),
+            | Self::VideoList
+            | Self::DownloadList
+            | Self::LocalPlaylistList => Constraint::Min(9),
             Self::SearchFilters => Constraint::Length(5),
+            Self::NowPlaying => Constraint::Min(30),
         }
     }
 
@@ -247,10 +277,14 @@ impl PageItems {
             | Self::MessageBar
             | Self::SearchBar
             | Self::SearchFilters => Constraint::Length(3),
-            Self::ItemList | Self::SingleItemInfo | Self::ChannelDisplay | Self::VideoList => {
-                Constraint::Min(6)
-            }
+            Self::ItemList
+            | Self::SingleItemInfo
+            | Self::ChannelDisplay
+            | Self::VideoList
+            | Self::DownloadList
+            | Self::LocalPlaylistList => Constraint::Min(6),
             Self::ChannelList => Constraint::Length(18),
+            Self::NowPlaying => Constraint::Min(8),
         }
     }
 }
@@ -355,3 +389,95 @@ fn feed_default() -> PageConfig {
         command: String::from("key Esc 0 ;; key Down 0 ;; key Down 0 ;; key Enter 0"),
     }
 }
+
+fn timeline_default() -> PageConfig {
+    PageConfig {
+        layout: vec![
+            PageRow::from_vec(vec![PageItems::SearchBar, PageItems::SearchFilters], false),
+            PageRow::from_vec(
+                vec![PageItems::Library, PageItems::Feed, PageItems::History],
+                true,
+            ),
+            PageRow::from_vec(vec![PageItems::ItemList], false),
+            PageRow::from_vec(vec![PageItems::MessageBar], false),
+        ],
+        message: String::from("Loading timeline..."),
+        command: String::from("key Esc 0 ;; key Down 0 ;; key Down 0 ;; key Enter 0"),
+    }
+}
+
+fn queue_default() -> PageConfig {
+    PageConfig {
+        layout: vec![
+            PageRow::from_vec(vec![PageItems::SearchBar, PageItems::SearchFilters], false),
+            PageRow::from_vec(
+                vec![PageItems::Library, PageItems::Feed, PageItems::History],
+                true,
+            ),
+            PageRow::from_vec(vec![PageItems::ItemList], false),
+            PageRow::from_vec(vec![PageItems::MessageBar], false),
+        ],
+        message: String::from("Loading queue..."),
+        command: String::from("key Esc 0 ;; key Down 0 ;; key Down 0 ;; key Enter 0"),
+    }
+}
+
+fn now_playing_default() -> PageConfig {
+    PageConfig {
+        layout: vec![
+            PageRow::from_vec(vec![PageItems::SearchBar, PageItems::SearchFilters], false),
+            PageRow::from_vec(vec![PageItems::NowPlaying], false),
+            PageRow::from_vec(vec![PageItems::MessageBar], false),
+        ],
+        message: String::from("Loading player..."),
+        command: String::from("key Esc 0 ;; key Down 0"),
+    }
+}
+
+fn downloads_default() -> PageConfig {
+    PageConfig {
+        layout: vec![
+            PageRow::from_vec(vec![PageItems::SearchBar, PageItems::SearchFilters], false),
+            PageRow::from_vec(
+                vec![PageItems::Library, PageItems::Feed, PageItems::History],
+                true,
+            ),
+            PageRow::from_vec(vec![PageItems::DownloadList], false),
+            PageRow::from_vec(vec![PageItems::MessageBar], false),
+        ],
+        message: String::from("Loading downloads..."),
+        command: String::from("key Esc 0 ;; key Down 0 ;; key Down 0 ;; key Enter 0"),
+    }
+}
+
+fn local_playlists_default() -> PageConfig {
+    PageConfig {
+        layout: vec![
+            PageRow::from_vec(vec![PageItems::SearchBar, PageItems::SearchFilters], false),
+            PageRow::from_vec(
+                vec![PageItems::Library, PageItems::Feed, PageItems::History],
+                true,
+            ),
+            PageRow::from_vec(vec![PageItems::LocalPlaylistList], false),
+            PageRow::from_vec(vec![PageItems::MessageBar], false),
+        ],
+        message: String::from("Loading playlists..."),
+        command: String::from("key Esc 0 ;; key Down 0 ;; key Down 0 ;; key Enter 0"),
+    }
+}
+
+fn local_playlist_default() -> PageConfig {
+    PageConfig {
+        layout: vec![
+            PageRow::from_vec(vec![PageItems::SearchBar, PageItems::SearchFilters], false),
+            PageRow::from_vec(
+                vec![PageItems::Library, PageItems::Feed, PageItems::History],
+                true,
+            ),
+            PageRow::from_vec(vec![PageItems::ItemList], false),
+            PageRow::from_vec(vec![PageItems::MessageBar], false),
+        ],
+        message: String::from("Loading playlist..."),
+        command: String::from("key Esc 0 ;; key Down 0 ;; key Down 0 ;; key Enter 0"),
+    }
+}
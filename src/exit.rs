@@ -13,7 +13,7 @@ use crate::{
 /// function to run when the app ends
 // exit tasks:
 //  - move thumbnails of videos in watch history to `~/.local/share/youtube-tui/watch_history/thumbnails`
-//  - remove `~/.cache`
+//  - remove `~/.cache`, except for cached responses
 pub fn exit(framework: &mut Framework) -> Result<(), Box<dyn Error>> {
     let limits = framework.data.global.get::<MainConfig>().unwrap().limits;
     let watchhistory = framework.data.global.get_mut::<WatchHistory>().unwrap();
@@ -24,18 +24,40 @@ pub fn exit(framework: &mut Framework) -> Result<(), Box<dyn Error>> {
     subscriptions.exit_move();
     let _ = subscriptions.save();
     framework.data.global.get::<Library>().unwrap().exit_move();
+    let queue = framework.data.global.get::<Queue>().unwrap();
+    queue.exit_move();
+    let _ = queue.save();
+    let localplaylists = framework.data.global.get::<LocalPlaylists>().unwrap();
+    localplaylists.exit_move();
+    let _ = localplaylists.save();
+    let _ = framework.data.global.get::<Positions>().unwrap().save();
+    let downloads = framework.data.global.get_mut::<Downloads>().unwrap();
+    downloads.stop_all();
+    let _ = downloads.save();
     let searchhistory = framework.data.global.get_mut::<SearchHistory>().unwrap();
     searchhistory.trim(limits.search_history);
     let _ = searchhistory.save();
     let commandhistory = framework.data.global.get_mut::<CommandHistory>().unwrap();
     commandhistory.trim(limits.commands_history);
     let _ = commandhistory.save();
+    let _ = framework.data.global.get::<ResponseCache>().unwrap().save();
 
     let home_dir = home_dir().unwrap();
     let cache_path = home_dir.join(".cache/youtube-tui/");
 
     if cache_path.exists() {
-        fs::remove_dir_all(cache_path)?;
+        for entry in fs::read_dir(cache_path)? {
+            let path = entry?.path();
+            if path.ends_with("responses") {
+                continue;
+            }
+
+            if path.is_dir() {
+                fs::remove_dir_all(path)?;
+            } else {
+                fs::remove_file(path)?;
+            }
+        }
     }
 
     Ok(())
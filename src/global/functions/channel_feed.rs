@@ -0,0 +1,218 @@
+use crate::global::{
+    functions::{date_text, httpreq_get_timeout, viewcount_text, xml_attribute, xml_text},
+    structs::MiniVideoItem,
+};
+use chrono::DateTime;
+use std::{error::Error, time::Duration};
+
+/// Fetch the latest videos of a channel from its Atom feed, `feed_url` is the url with the channel
+/// id left out (e.g. `https://www.youtube.com/feeds/videos.xml?channel_id=`)
+pub fn channel_feed(
+    feed_url: &str,
+    id: &str,
+    timeout: Duration,
+) -> Result<Vec<MiniVideoItem>, Box<dyn Error>> {
+    let body = httpreq_get_timeout(&format!("{feed_url}{id}"), timeout)?;
+    let mut videos = parse_feed(&String::from_utf8_lossy(&body))?;
+    videos.sort();
+    Ok(videos)
+}
+
+/// Videos from the Atom feed if `feed_url` is set, `fallback` is used instead if it is not, or if
+/// the feed cannot be fetched
+pub fn feed_or_else(
+    feed_url: Option<&str>,
+    id: &str,
+    timeout: Duration,
+    fallback: impl FnOnce() -> Result<Vec<MiniVideoItem>, Box<dyn Error>>,
+) -> Result<Vec<MiniVideoItem>, Box<dyn Error>> {
+    match feed_url.map(|feed_url| channel_feed(feed_url, id, timeout)) {
+        Some(Ok(videos)) => Ok(videos),
+        _ => fallback(),
+    }
+}
+
+/// Parse videos from a YouTube Atom feed, the feed does not contain video lengths
+pub fn parse_feed(xml: &str) -> Result<Vec<MiniVideoItem>, Box<dyn Error>> {
+    if !xml.contains("<feed") {
+        return Err("Response is not an Atom feed".into());
+    }
+
+    Ok(xml
+        .split("<entry>")
+        .skip(1)
+        .filter_map(|entry| {
+            let entry = &entry[..entry.find("</entry>")?];
+            let id = xml_text(entry, "yt:videoId")?;
+            let timestamp = xml_text(entry, "published")
+                .and_then(|published| DateTime::parse_from_rfc3339(&published).ok())
+                .map(|published| published.timestamp() as u64);
+            let author = entry
+                .find("<author>")
+                .map(|start| &entry[start..])
+                .unwrap_or_default();
+
+            Some(MiniVideoItem {
+                title: xml_text(entry, "title")?,
+                thumbnail_url: element(entry, "media:thumbnail")
+                    .and_then(|tag| xml_attribute(tag, "url"))
+                    .unwrap_or_else(|| format!("https://i.ytimg.com/vi/{id}/mqdefault.jpg")),
+                length: String::from("Unknown"),
+                views: element(entry, "media:statistics")
+                    .and_then(|tag| xml_attribute(tag, "views"))
+                    .and_then(|views| views.parse().ok())
+                    .map(viewcount_text),
+                channel: xml_text(author, "name").unwrap_or_default(),
+                channel_id: xml_text(entry, "yt:channelId").unwrap_or_default(),
+                published: timestamp.map(date_text),
+                timestamp,
+                description: xml_text(entry, "media:description")
+                    .filter(|description| !description.is_empty()),
+                id,
+            })
+        })
+        .collect())
+}
+
+/// the opening tag of the first element with the tag name
+fn element<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let start = xml.find(&format!("<{name} "))?;
+    let len = xml[start..].find('>')?;
+    Some(&xml[start..start + len])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    /// a recorded feed with 3 videos, trimmed
+    const FEED: &str = include_str!("../../../tests/fixtures/feed.xml");
+    const CHANNEL_ID: &str = "UCexampleChannel000000000";
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// a local stand-in for the feed server that answers one request with `status` and `body`,
+    /// returns the feed url to use
+    fn serve(status: &'static str, body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                match stream.read(&mut buffer) {
+                    Ok(0) | Err(_) => break,
+                    Ok(read) => request.extend_from_slice(&buffer[..read]),
+                }
+            }
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Type: application/atom+xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        });
+
+        format!("http://127.0.0.1:{port}/feeds/videos.xml?channel_id=")
+    }
+
+    fn fallback_video() -> MiniVideoItem {
+        MiniVideoItem {
+            title: String::from("From Invidious"),
+            id: String::from("invidious01"),
+            thumbnail_url: String::new(),
+            length: String::from("1:00"),
+            views: None,
+            channel: String::new(),
+            channel_id: String::from(CHANNEL_ID),
+            published: None,
+            timestamp: Some(0),
+            description: None,
+        }
+    }
+
+    fn find<'a>(videos: &'a [MiniVideoItem], id: &str) -> &'a MiniVideoItem {
+        videos.iter().find(|video| video.id == id).unwrap()
+    }
+
+    #[test]
+    fn parse_feed_unescapes_titles() {
+        let videos = parse_feed(FEED).unwrap();
+        assert_eq!(videos.len(), 3);
+
+        let video = find(&videos, "vid00000003");
+        assert_eq!(video.title, "Tom & Jerry's \"Best\" <Moments>");
+        assert_eq!(video.channel, "Example & Co");
+        assert_eq!(video.channel_id, CHANNEL_ID);
+        assert_eq!(video.description.as_deref(), Some("Cats & mice."));
+        assert_eq!(
+            video.thumbnail_url,
+            "https://i2.ytimg.com/vi/vid00000003/hqdefault.jpg"
+        );
+        assert_eq!(video.views.as_deref(), Some("123.46K"));
+        assert_eq!(video.timestamp, Some(1714752007));
+        // feeds have no lengths
+        assert_eq!(video.length, "Unknown");
+    }
+
+    #[test]
+    fn parse_feed_without_published_date() {
+        let videos = parse_feed(FEED).unwrap();
+
+        let video = find(&videos, "vid00000002");
+        assert_eq!(video.timestamp, None);
+        assert_eq!(video.published, None);
+        assert_eq!(video.views, None);
+        assert_eq!(video.description, None);
+        assert_eq!(
+            video.thumbnail_url,
+            "https://i.ytimg.com/vi/vid00000002/mqdefault.jpg"
+        );
+    }
+
+    #[test]
+    fn parse_feed_rejects_other_responses() {
+        assert!(parse_feed("<html><body>Not found</body></html>").is_err());
+    }
+
+    #[test]
+    fn channel_feed_from_stand_in() {
+        let videos = channel_feed(&serve("200 OK", FEED), CHANNEL_ID, TIMEOUT).unwrap();
+        assert_eq!(videos.len(), 3);
+        assert_eq!(find(&videos, "vid00000001").title, "First upload");
+    }
+
+    #[test]
+    fn feed_or_else_uses_feed() {
+        let videos = feed_or_else(Some(&serve("200 OK", FEED)), CHANNEL_ID, TIMEOUT, || {
+            panic!("fell back to invidious")
+        })
+        .unwrap();
+        assert_eq!(videos.len(), 3);
+    }
+
+    #[test]
+    fn feed_or_else_falls_back_on_http_error() {
+        let videos = feed_or_else(
+            Some(&serve("500 Internal Server Error", "Internal Server Error")),
+            CHANNEL_ID,
+            TIMEOUT,
+            || Ok(vec![fallback_video()]),
+        )
+        .unwrap();
+        assert_eq!(videos.len(), 1);
+        assert_eq!(videos[0].id, fallback_video().id);
+    }
+
+    #[test]
+    fn feed_or_else_falls_back_without_feed_url() {
+        let videos =
+            feed_or_else(None, CHANNEL_ID, TIMEOUT, || Ok(vec![fallback_video()])).unwrap();
+        assert_eq!(videos.len(), 1);
+        assert_eq!(videos[0].id, fallback_video().id);
+    }
+}
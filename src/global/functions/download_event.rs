@@ -0,0 +1,80 @@
+use crate::{
+    config::MainConfig,
+    global::{functions::find_library_item, structs::*, traits::Collection},
+};
+use tui_additions::framework::Framework;
+
+/// handles an event from a downloader process, updating the job it belongs to
+pub fn download_event(event: DownloadEvent, framework: &mut Framework) {
+    let downloads = framework.data.global.get_mut::<Downloads>().unwrap();
+    match event {
+        DownloadEvent::Progress(job, progress) => {
+            if let Some(job) = downloads.get_mut(job) {
+                job.progress = Some(progress);
+            }
+        }
+        DownloadEvent::Error(job, e) => {
+            if let Some(job) = downloads.get_mut(job) {
+                job.error = Some(e);
+            }
+        }
+        DownloadEvent::Finished(job, success) => {
+            let job = match downloads.get_mut(job) {
+                Some(job) => job.clone(),
+                None => return,
+            };
+
+            // the downloader may exit successfully without actually saving anything, so only
+            // count it as saved if the file is there
+            let mut downloads = framework.data.global.remove::<Downloads>().unwrap();
+            let mainconfig = framework.data.global.get::<MainConfig>().unwrap();
+            let result = if !success {
+                Err(job
+                    .error
+                    .clone()
+                    .unwrap_or_else(|| String::from("Downloader exited with an error")))
+            } else if find_library_item(&job.id, mainconfig).is_none() {
+                Err(String::from("Downloaded file not found"))
+            } else {
+                Ok(())
+            };
+
+            let state = downloads.finished(job.job, result, mainconfig);
+            // a slot is freed up for the next job in queue
+            downloads.start_queued(mainconfig);
+            let _ = downloads.save();
+            framework.data.global.insert::<Downloads>(downloads);
+
+            let message = match state {
+                Some(DownloadState::Done) => {
+                    let library = framework.data.global.get_mut::<Library>().unwrap();
+                    let _ = library.push(job.item);
+                    let _ = library.save();
+                    Some(Message::Success(format!("Downloaded {}", job.title)))
+                }
+                Some(DownloadState::Queued) => Some(Message::Error(format!(
+                    "Failed to download {}, retrying",
+                    job.title
+                ))),
+                Some(DownloadState::Failed(_)) => {
+                    Some(Message::Error(format!("Failed to download {}", job.title)))
+                }
+                // paused and cancelled jobs already have a message from the command
+                _ => None,
+            };
+            if let Some(message) = message {
+                *framework.data.global.get_mut::<Message>().unwrap() = message;
+            }
+        }
+    }
+
+    if framework.data.state.get::<Page>().unwrap() == &Page::Downloads {
+        framework
+            .data
+            .state
+            .get_mut::<Tasks>()
+            .unwrap()
+            .priority
+            .push(Task::RenderAll);
+    }
+}
@@ -0,0 +1,10 @@
+use home::home_dir;
+use std::path::PathBuf;
+
+/// Turns a path from the command line into a `PathBuf`, expanding `~/` into the home directory
+pub fn expand_path(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(path) => home_dir().unwrap().join(path),
+        None => PathBuf::from(path),
+    }
+}
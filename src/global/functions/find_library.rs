@@ -1,16 +1,46 @@
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 use home::home_dir;
 
 use crate::config::MainConfig;
 
-pub fn find_library_item(id: &str, mainconfig: &MainConfig) -> Option<PathBuf> {
-    fs::read_dir(
+/// extensions of files left behind by unfinished downloads
+const PARTIAL_EXTENSIONS: &[&str] = &["part", "ytdl", "temp"];
+
+/// full path to `save-path` in `env` of the main config
+pub fn save_path(mainconfig: &MainConfig) -> Option<PathBuf> {
+    Some(
         home_dir()
             .unwrap()
             .join(mainconfig.env.get("save-path")?.replacen("~/", "", 1)),
     )
-    .ok()?
-    .filter_map(|entry| Some(entry.ok()?.path()))
-    .find(|stem| stem.as_os_str().to_str().unwrap_or_default().contains(id))
+}
+
+/// id of a saved file or playlist folder named `title[id].ext` or `title[id]`
+pub fn saved_id(path: &Path) -> Option<&str> {
+    let name = path.file_name()?.to_str()?;
+    let end = name.rfind(']')?;
+    let start = name[..end].rfind('[')?;
+    Some(&name[start + 1..end]).filter(|id| !id.is_empty())
+}
+
+/// whether the file is left behind by an unfinished download
+pub fn is_partial(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            PARTIAL_EXTENSIONS
+                .iter()
+                .any(|partial| extension.starts_with(partial))
+        })
+}
+
+pub fn find_library_item(id: &str, mainconfig: &MainConfig) -> Option<PathBuf> {
+    fs::read_dir(save_path(mainconfig)?)
+        .ok()?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .find(|path| saved_id(path) == Some(id) && !is_partial(path))
 }
@@ -0,0 +1,27 @@
+use http_req::{request::Request, uri::Uri};
+use std::{error::Error, time::Duration};
+
+/// longest delay between retries, however many times a request is retried
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(300);
+
+/// GET request that gives up if connecting or reading the response stalls for `timeout`, so a
+/// request to an unresponsive server never blocks the thread it runs in
+pub fn httpreq_get_timeout(url: &str, timeout: Duration) -> Result<Vec<u8>, Box<dyn Error>> {
+    let uri = Uri::try_from(url)?;
+    let mut body = Vec::new();
+    let res = Request::new(&uri)
+        .connect_timeout(Some(timeout))
+        .read_timeout(Some(timeout))
+        .send(&mut body)?;
+
+    if !res.status_code().is_success() {
+        return Err(format!("request failed: {}", res.reason()).into());
+    }
+    Ok(body)
+}
+
+/// delay before retry number `attempt` (starting from 0), doubled after each retry but never
+/// longer than 5 minutes
+pub fn retry_delay(base_secs: u64, attempt: u32) -> Duration {
+    Duration::from_secs(base_secs.saturating_mul(1 << attempt.min(16))).min(MAX_RETRY_DELAY)
+}
@@ -0,0 +1,227 @@
+use crate::{config::MainConfig, global::structs::*};
+use std::error::Error;
+use tui_additions::framework::{Framework, FrameworkData};
+
+/// use another instance in `Instances`, the page should be reloaded afterwards
+pub fn switch_instance(data: &mut FrameworkData, index: usize) {
+    let instances = data.global.get_mut::<Instances>().unwrap();
+    instances.current = index;
+    let url = instances.current_url().to_string();
+
+    data.global
+        .insert::<InvidiousClient>(InvidiousClient::new(url.clone()));
+    data.global
+        .get_mut::<MainConfig>()
+        .unwrap()
+        .invidious_instance = url;
+    data.global.get_mut::<Status>().unwrap().provider_updated = true;
+}
+
+/// turn offline mode on or off, the page should be reloaded afterwards
+pub fn set_offline(data: &mut FrameworkData, offline: bool) {
+    data.global.get_mut::<ResponseCache>().unwrap().offline = offline;
+    data.global.get_mut::<Instances>().unwrap().failures = 0;
+}
+
+/// load the current page, trying the fallback instances in order if loading fails, returns a
+/// message if the TUI switched to another instance or to offline mode
+///
+/// if the instance in use still responds to a health check, the error is about the content (e.g.
+/// an unavailable video) and is returned without failing over. Fallbacks that are known to be
+/// down are skipped, and the others are checked before loading from them, so each unreachable
+/// instance only takes up to `check_timeout_secs`
+pub fn load_with_failover(framework: &mut Framework) -> Result<Option<String>, Box<dyn Error>> {
+    let online = framework.data.state.get::<Page>().unwrap().uses_invidious()
+        && !framework
+            .data
+            .global
+            .get::<ResponseCache>()
+            .unwrap()
+            .offline;
+    let e = match framework.load() {
+        Ok(()) => {
+            if online {
+                framework
+                    .data
+                    .global
+                    .get_mut::<Instances>()
+                    .unwrap()
+                    .failures = 0;
+            }
+            return Ok(None);
+        }
+        Err(e) => e,
+    };
+    if !online {
+        return Err(e);
+    }
+
+    let config = &framework.data.global.get::<MainConfig>().unwrap().instances;
+    let (threshold, timeout) = (config.offline_after_failures, config.check_timeout_secs);
+    let instances = framework.data.global.get_mut::<Instances>().unwrap();
+    let original = instances.current;
+    if matches!(
+        instances.check_now(original, timeout),
+        InstanceHealth::Healthy(_)
+    ) {
+        return Err(e);
+    }
+
+    for index in instances.fallbacks() {
+        let instances = framework.data.global.get_mut::<Instances>().unwrap();
+        if !matches!(
+            instances.check_now(index, timeout),
+            InstanceHealth::Healthy(_)
+        ) {
+            continue;
+        }
+
+        switch_instance(&mut framework.data, index);
+        if framework.load().is_ok() {
+            let instances = framework.data.global.get_mut::<Instances>().unwrap();
+            instances.failures = 0;
+            return Ok(Some(format!(
+                "Failed to load from instance, switched to {}",
+                instances.current_url()
+            )));
+        }
+    }
+
+    if framework.data.global.get::<Instances>().unwrap().current != original {
+        switch_instance(&mut framework.data, original);
+    }
+
+    // the instance in use has already failed the health check
+    let instances = framework.data.global.get_mut::<Instances>().unwrap();
+    instances.failures += 1;
+    if threshold == 0 || instances.failures < threshold {
+        return Err(e);
+    }
+
+    let failures = instances.failures;
+    set_offline(&mut framework.data, true);
+    match framework.load() {
+        Ok(()) => Ok(Some(format!(
+            "{failures} pages failed to load, switched to offline mode"
+        ))),
+        Err(_) => Err(format!("{e}, switched to offline mode").into()),
+    }
+}
+
+/// run a request outside of page loading (e.g. comments) against the instance in use, failing
+/// over to the fallback instances and to offline mode the same way as `load_with_failover`
+pub fn request_with_failover<T>(
+    data: &mut FrameworkData,
+    mut request: impl FnMut(&str) -> Result<T, Box<dyn Error>>,
+) -> Result<T, Box<dyn Error>> {
+    if data.global.get::<ResponseCache>().unwrap().offline {
+        return Err("Unavailable in offline mode".into());
+    }
+
+    let e = match request(data.global.get::<Instances>().unwrap().current_url()) {
+        Ok(value) => {
+            data.global.get_mut::<Instances>().unwrap().failures = 0;
+            return Ok(value);
+        }
+        Err(e) => e,
+    };
+
+    let config = &data.global.get::<MainConfig>().unwrap().instances;
+    let (threshold, timeout) = (config.offline_after_failures, config.check_timeout_secs);
+    let instances = data.global.get_mut::<Instances>().unwrap();
+    let original = instances.current;
+    if matches!(
+        instances.check_now(original, timeout),
+        InstanceHealth::Healthy(_)
+    ) {
+        return Err(e);
+    }
+
+    for index in instances.fallbacks() {
+        let instances = data.global.get_mut::<Instances>().unwrap();
+        if !matches!(
+            instances.check_now(index, timeout),
+            InstanceHealth::Healthy(_)
+        ) {
+            continue;
+        }
+
+        switch_instance(data, index);
+        if let Ok(value) = request(data.global.get::<Instances>().unwrap().current_url()) {
+            let instances = data.global.get_mut::<Instances>().unwrap();
+            instances.failures = 0;
+            let message = format!(
+                "Failed to load from instance, switched to {}",
+                instances.current_url()
+            );
+            *data.global.get_mut::<Message>().unwrap() = Message::Message(message);
+            return Ok(value);
+        }
+    }
+
+    if data.global.get::<Instances>().unwrap().current != original {
+        switch_instance(data, original);
+    }
+
+    let instances = data.global.get_mut::<Instances>().unwrap();
+    instances.failures += 1;
+    if threshold == 0 || instances.failures < threshold {
+        return Err(e);
+    }
+
+    set_offline(data, true);
+    Err(format!("{e}, switched to offline mode").into())
+}
+
+/// handles the result of an instance health check or discovery
+pub fn instance_event(event: InstanceEvent, framework: &mut Framework) {
+    let message = match event {
+        InstanceEvent::Checked(url, health) => {
+            let instances = framework.data.global.get_mut::<Instances>().unwrap();
+            // the instance may be removed by reloading configs
+            if !instances.set_health(&url, health) || instances.testing == 0 {
+                return;
+            }
+
+            instances.testing -= 1;
+            if instances.testing != 0 {
+                return;
+            }
+
+            Message::Message(format!(
+                "{}: {}",
+                instances.summary(),
+                instances.list_text()
+            ))
+        }
+        InstanceEvent::Discovered(Ok(Some((saved, discovered)))) => {
+            let mainconfig = framework.data.global.get_mut::<MainConfig>().unwrap();
+            mainconfig.invidious_instance = saved.invidious_instance;
+            mainconfig.instances.fallbacks = saved.instances.fallbacks;
+            let instances = Instances::new(mainconfig);
+            framework.data.global.insert::<Instances>(instances);
+            switch_instance(&mut framework.data, 0);
+            framework
+                .data
+                .state
+                .get_mut::<Tasks>()
+                .unwrap()
+                .priority
+                .push(Task::Reload);
+            Message::Success(discovered.to_string())
+        }
+        InstanceEvent::Discovered(Ok(None)) => {
+            Message::Error(String::from("No healthy instances found"))
+        }
+        InstanceEvent::Discovered(Err(e)) => Message::Error(format!("Discovery failed: {e}")),
+    };
+
+    *framework.data.global.get_mut::<Message>().unwrap() = message;
+    framework
+        .data
+        .state
+        .get_mut::<Tasks>()
+        .unwrap()
+        .priority
+        .push(Task::RenderAll);
+}
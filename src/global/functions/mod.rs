@@ -3,6 +3,8 @@ mod secs_display_string;
 pub use secs_display_string::*;
 mod viewcount_text;
 pub use viewcount_text::*;
+mod size_text;
+pub use size_text::*;
 mod date_text;
 pub use date_text::*;
 mod download_all_images;
@@ -21,6 +23,14 @@ mod clipboard;
 pub use self::clipboard::*;
 mod from_url;
 pub use from_url::*;
+mod expand_path;
+pub use expand_path::*;
+mod xml;
+pub use xml::*;
+mod channel_feed;
+pub use channel_feed::*;
+mod httpreq_get_timeout;
+pub use httpreq_get_timeout::*;
 mod envs;
 pub use envs::*;
 mod update_provider;
@@ -35,3 +45,15 @@ mod key_input;
 pub use key_input::*;
 mod find_library;
 pub use find_library::*;
+mod download_event;
+pub use download_event::*;
+mod sync_event;
+pub use sync_event::*;
+mod instance_event;
+pub use instance_event::*;
+mod notify_new_videos;
+pub use notify_new_videos::*;
+#[cfg(feature = "mpv")]
+mod mpv_event;
+#[cfg(feature = "mpv")]
+pub use mpv_event::*;
@@ -0,0 +1,126 @@
+use crate::{
+    config::MainConfig,
+    global::{structs::*, traits::Collection},
+};
+use tui_additions::framework::Framework;
+
+/// handles an event from the mpv event listener, pushing tasks to update the TUI
+pub fn mpv_event(event: MpvEvent, framework: &mut Framework) {
+    let render = match event {
+        MpvEvent::MediaTitle(title) => {
+            framework
+                .data
+                .global
+                .get_mut::<MpvWrapper>()
+                .unwrap()
+                .player
+                .media_title = title;
+            true
+        }
+        MpvEvent::Property { name, value } => {
+            framework
+                .data
+                .global
+                .get_mut::<MpvWrapper>()
+                .unwrap()
+                .player
+                .properties
+                .insert(name, value);
+            framework.data.state.get::<Page>().unwrap() == &Page::NowPlaying
+        }
+        // the chapter list only changes when a new file is loaded, so it is kept until then
+        MpvEvent::FileLoaded => {
+            let mpv = framework.data.global.get_mut::<MpvWrapper>().unwrap();
+            let property = |name: String| mpv.property(name);
+            let count = property(String::from("chapter-list/count"))
+                .and_then(|count| count.parse::<usize>().ok())
+                .unwrap_or_default();
+            let chapters = (0..count)
+                .map(|index| {
+                    (
+                        property(format!("chapter-list/{index}/time"))
+                            .and_then(|time| time.parse().ok())
+                            .unwrap_or_default(),
+                        property(format!("chapter-list/{index}/title")).unwrap_or_default(),
+                    )
+                })
+                .collect();
+            let path = property(String::from("path"));
+
+            mpv.player.chapters = chapters;
+            mpv.player.path = path;
+            framework.data.state.get::<Page>().unwrap() == &Page::NowPlaying
+        }
+        MpvEvent::Pause(paused) => {
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Mpv(String::from(if paused {
+                    "Player paused"
+                } else {
+                    "Player resumed"
+                }));
+            true
+        }
+        MpvEvent::PlaylistPos(pos) => {
+            // items in the queue are added to watch history when they start playing
+            let playing = framework
+                .data
+                .global
+                .get::<Queue>()
+                .unwrap()
+                .playing(framework.data.global.get::<MpvWrapper>().unwrap());
+            if let Some(item) = playing
+                .filter(|playing| *playing as i64 == pos)
+                .map(|playing| framework.data.global.get::<Queue>().unwrap().0[playing].clone())
+            {
+                let watchhistory = framework.data.global.get_mut::<WatchHistory>().unwrap();
+                let _ = watchhistory.push(item);
+                let _ = watchhistory.save();
+            }
+
+            framework.data.state.get::<Page>().unwrap() == &Page::Queue
+        }
+        MpvEvent::TimePos(_) => {
+            let resume = framework
+                .data
+                .global
+                .get::<MainConfig>()
+                .unwrap()
+                .resume_playback;
+            // taken out of the typemap to borrow mpv at the same time
+            let mut positions = framework.data.global.remove::<Positions>().unwrap();
+            positions.update(framework.data.global.get::<MpvWrapper>().unwrap(), resume);
+            framework.data.global.insert::<Positions>(positions);
+            false
+        }
+        MpvEvent::EndFile => {
+            let player = &mut framework
+                .data
+                .global
+                .get_mut::<MpvWrapper>()
+                .unwrap()
+                .player;
+            player.path = None;
+            player.chapters.clear();
+            let positions = framework.data.global.get_mut::<Positions>().unwrap();
+            // so the same video is seeked to its position again if it is replayed
+            positions.current = None;
+            let _ = positions.save();
+            true
+        }
+        MpvEvent::Error(e) => {
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Error(format!("Playback failed: {e}"));
+            true
+        }
+    };
+
+    if render {
+        framework
+            .data
+            .state
+            .get_mut::<Tasks>()
+            .unwrap()
+            .priority
+            .push(Task::RenderAll);
+    }
+}
@@ -0,0 +1,34 @@
+use crate::{
+    config::MainConfig,
+    global::structs::{MiniVideoItem, Queue},
+};
+use std::{
+    process::{Command, Stdio},
+    thread,
+};
+
+/// Runs `notify_command` for each new video found when syncing, with `${title}`, `${channel}` and
+/// `${url}` set as environment variables of the command, invidious urls point to `instance`
+pub fn notify_new_videos(videos: &[MiniVideoItem], mainconfig: &MainConfig, instance: &str) {
+    if mainconfig.notify_command.is_empty() {
+        return;
+    }
+
+    videos.iter().for_each(|video| {
+        let child = Command::new(&mainconfig.shell)
+            .args(["-c", &mainconfig.notify_command])
+            .env("title", &video.title)
+            .env("channel", &video.channel)
+            .env("url", Queue::url(&video.id, mainconfig.provider, instance))
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+
+        // waited on in the background so finished commands are not left as zombies
+        if let Ok(mut child) = child {
+            thread::spawn(move || {
+                let _ = child.wait();
+            });
+        }
+    });
+}
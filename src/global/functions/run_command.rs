@@ -12,6 +12,7 @@ use std::{
     fs,
     io::Stdout,
     process::{Command, Stdio},
+    sync::mpsc,
 };
 use tui_additions::framework::Framework;
 
@@ -30,10 +31,101 @@ pub fn text_command(command: &str) -> Option<String> {
             env!("CARGO_PKG_NAME"),
             env!("CARGO_PKG_VERSION")
         )),
+        ["sync"] => Some(
+            match (|| -> Result<String, Box<dyn Error>> {
+                let mainconfig = MainConfig::load(WriteConfig::Dont)?;
+                let filters = FeedFilters::try_from(*FeedFiltersConfig::load(WriteConfig::Dont)?)?;
+                let instances = Instances::new(&mainconfig).urls();
+                let mut subs = Subscriptions::load();
+                fs::create_dir_all(home_dir().unwrap().join(".local/share/youtube-tui/"))?;
+
+                // thumbnails are downloaded for the TUI to show, the cache is kept between sessions
+                let (sender, receiver) = mpsc::channel();
+                let mut progress = subs.sync(
+                    &instances,
+                    mainconfig.image_index,
+                    mainconfig.images.display(),
+                    mainconfig.syncing.clone(),
+                    filters,
+                    sender,
+                );
+                while !progress.is_done() {
+                    let synced = receiver.recv()?;
+                    if let SyncOutcome::Success(new_videos) = &synced.outcome {
+                        // the current instance is listed first
+                        notify_new_videos(new_videos, &mainconfig, &instances[0]);
+                    }
+                    subs.synced(synced, &mut progress);
+                }
+
+                Ok(progress.summary())
+            })() {
+                Ok(summary) => summary,
+                Err(e) => format!("Sync failed: {e}"),
+            },
+        ),
+        ["instance", "discover", region @ ..] if region.len() <= 1 => Some(
+            match (|| -> Result<String, Box<dyn Error>> {
+                let mut mainconfig = *MainConfig::load(WriteConfig::Dont)?;
+                let discovered = Discovered::fetch(region.first().copied(), &mainconfig)?;
+                if discovered.apply(&mut mainconfig) {
+                    mainconfig.save()?;
+                }
+                Ok(discovered.to_string())
+            })() {
+                Ok(summary) => summary,
+                Err(e) => format!("Discovery failed: {e}"),
+            },
+        ),
+        ["cache", args @ ..] => Some(match MainConfig::load(WriteConfig::Dont) {
+            Ok(mainconfig) => {
+                match cache_command(args, &ResponseCache::load(), &mainconfig.cache) {
+                    Ok(summary) | Err(summary) => summary,
+                }
+            }
+            Err(e) => format!("Cannot load main config: {e}"),
+        }),
+        ["subscriptions", "import", _, ..] => Some(
+            match (|| -> Result<ImportSummary, Box<dyn Error>> {
+                let mut subs = Subscriptions::load();
+                let summary = subs.import(command_remainder(command, 2))?;
+                fs::create_dir_all(home_dir().unwrap().join(".local/share/youtube-tui/"))?;
+                subs.save()?;
+                Ok(summary)
+            })() {
+                Ok(summary) => summary.to_string(),
+                Err(e) => format!("Import failed: {e}"),
+            },
+        ),
+        ["subscriptions", "export", format, _, ..] => {
+            Some(match SubscriptionsFormat::from_name(format) {
+                Some(format) => {
+                    match Subscriptions::load().export(command_remainder(command, 3), format) {
+                        Ok(count) => format!("Exported {count} channels as {format}"),
+                        Err(e) => format!("Export failed: {e}"),
+                    }
+                }
+                None => {
+                    format!("Unknown format `{format}`, expected opml, csv, newpipe or invidious")
+                }
+            })
+        }
         _ => None,
     }
 }
 
+/// the rest of a command after its first `n` words, file paths are taken from here instead of
+/// joining the words so that repeated spaces in them are kept
+fn command_remainder(command: &str, n: usize) -> &str {
+    (0..n)
+        .fold(command.trim_start(), |rest, _| {
+            rest.split_once(|c: char| c.is_ascii_whitespace())
+                .map_or("", |(_, rest)| rest)
+                .trim_start()
+        })
+        .trim_end()
+}
+
 pub fn run_command(
     command: &str,
     framework: &mut Framework,
@@ -117,14 +209,108 @@ pub fn run_single_command(
                 Message::Success(format!("Provider updated to {}", status.provider.as_str()));
             update_provider(&mut framework.data);
         }
+        ["instance", "list"] => {
+            let instances = framework.data.global.get::<Instances>().unwrap();
+            *framework.data.global.get_mut::<Message>().unwrap() = Message::Message(format!(
+                "{}: {}",
+                instances.summary(),
+                instances.list_text()
+            ));
+        }
+        ["instance", "test"] => {
+            let timeout = framework
+                .data
+                .global
+                .get::<MainConfig>()
+                .unwrap()
+                .instances
+                .check_timeout_secs;
+            let instances = framework.data.global.get_mut::<Instances>().unwrap();
+            // results are shown in the message bar once all instances are checked
+            instances.testing = instances.list.len();
+            instances.check_all(timeout);
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Message(format!("Testing {} instances...", instances.list.len()));
+        }
+        ["instance", "discover", region @ ..] if region.len() <= 1 => {
+            // the result is shown in the message bar once discovery finishes
+            framework
+                .data
+                .global
+                .get::<Instances>()
+                .unwrap()
+                .discover(region.first().map(|region| region.to_string()));
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Message(String::from("Discovering instances..."));
+        }
+        ["instance", "switch", instance] => {
+            let instances = framework.data.global.get_mut::<Instances>().unwrap();
+            let index = match instance.parse::<usize>() {
+                Ok(index) if (1..=instances.list.len()).contains(&index) => index - 1,
+                Ok(_) => {
+                    *framework.data.global.get_mut::<Message>().unwrap() =
+                        Message::Error(String::from("No instance at that position"));
+                    return;
+                }
+                // instances not in main config are only used until the TUI exits
+                Err(_) => {
+                    let url = instance.trim_end_matches('/');
+                    match instances
+                        .list
+                        .iter()
+                        .position(|(existing, _)| existing == url)
+                    {
+                        Some(index) => index,
+                        None => {
+                            instances
+                                .list
+                                .push((url.to_string(), InstanceHealth::Unknown));
+                            instances.list.len() - 1
+                        }
+                    }
+                }
+            };
+
+            switch_instance(&mut framework.data, index);
+            *framework.data.global.get_mut::<Message>().unwrap() = Message::Success(format!(
+                "Switched to {}",
+                framework
+                    .data
+                    .global
+                    .get::<Instances>()
+                    .unwrap()
+                    .current_url()
+            ));
+            framework
+                .data
+                .state
+                .get_mut::<Tasks>()
+                .unwrap()
+                .priority
+                .push(Task::Reload);
+        }
         // loads a given page
         ["loadpage", page, ..] => {
             let page = match *page {
                 "popular" => Some(Page::MainMenu(MainMenuPage::Popular)),
                 "trending" => Some(Page::MainMenu(MainMenuPage::Trending)),
                 "watchhistory" => Some(Page::MainMenu(MainMenuPage::History)),
-                "feed" => Some(Page::Feed),
+                "feed" => Some(Page::Feed(command.get(2).map(|group| group.to_string()))),
+                "timeline" => Some(Page::Timeline(
+                    command
+                        .get(2)
+                        .and_then(|page| page.parse().ok())
+                        .unwrap_or(1)
+                        .max(1),
+                )),
                 "library" => Some(Page::MainMenu(MainMenuPage::Library)),
+                "queue" => Some(Page::Queue),
+                "nowplaying" => Some(Page::NowPlaying),
+                "downloads" => Some(Page::Downloads),
+                "playlists" => Some(match command.get(2) {
+                    Some(name) => Page::LocalPlaylist(name.to_string()),
+                    None => Page::LocalPlaylists,
+                }),
                 "channel" => {
                     if command.len() == 2 {
                         *framework.data.global.get_mut::<Message>().unwrap() =
@@ -251,6 +437,51 @@ pub fn run_single_command(
                 .priority
                 .push(Task::Reload);
         }
+        ["offline", toggle @ ..] if toggle.len() <= 1 => {
+            let offline = match toggle {
+                [] => {
+                    !framework
+                        .data
+                        .global
+                        .get::<ResponseCache>()
+                        .unwrap()
+                        .offline
+                }
+                ["on"] => true,
+                ["off"] => false,
+                _ => {
+                    *framework.data.global.get_mut::<Message>().unwrap() = Message::Error(
+                        String::from("Expected `offline`, `offline on` or `offline off`"),
+                    );
+                    return;
+                }
+            };
+
+            set_offline(&mut framework.data, offline);
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Success(String::from(if offline {
+                    "Offline mode, pages are loaded from cache"
+                } else {
+                    "Back online"
+                }));
+            framework
+                .data
+                .state
+                .get_mut::<Tasks>()
+                .unwrap()
+                .priority
+                .push(Task::Reload);
+        }
+        ["cache", args @ ..] => {
+            *framework.data.global.get_mut::<Message>().unwrap() = match cache_command(
+                args,
+                framework.data.global.get::<ResponseCache>().unwrap(),
+                &framework.data.global.get::<MainConfig>().unwrap().cache,
+            ) {
+                Ok(summary) => Message::Success(summary),
+                Err(e) => Message::Error(e),
+            };
+        }
         ["reload", "configs"] => {
             *framework.data.global.get_mut::<Message>().unwrap() =
                 match load_configs(&mut framework.split_clean().0) {
@@ -327,12 +558,7 @@ pub fn run_single_command(
                 }
             };
 
-            let client = framework
-                .data
-                .global
-                .get::<InvidiousClient>()
-                .unwrap()
-                .clone();
+            let instances = framework.data.global.get::<Instances>().unwrap().urls();
 
             *framework.data.global.get_mut::<Message>().unwrap() =
                 Message::Message(String::from("Syncing..."));
@@ -341,11 +567,35 @@ pub fn run_single_command(
             let mainconfig = framework.data.global.get::<MainConfig>().unwrap();
             let image_index = mainconfig.image_index;
             let download_thumbnails = mainconfig.images.display();
-            let syncing = mainconfig.syncing;
+            let syncing = mainconfig.syncing.clone();
+            let filters = framework.data.global.get::<FeedFilters>().unwrap().clone();
 
             let subscriptions = framework.data.global.get_mut::<Subscriptions>().unwrap();
-            match subscriptions.sync_one(&id, &client, image_index, download_thumbnails, &syncing) {
-                Ok(()) => {
+            match subscriptions.sync_one(
+                &id,
+                &instances,
+                image_index,
+                download_thumbnails,
+                &syncing,
+                &filters,
+            ) {
+                Ok(new_videos) => {
+                    notify_new_videos(
+                        &new_videos,
+                        framework.data.global.get::<MainConfig>().unwrap(),
+                        framework
+                            .data
+                            .global
+                            .get::<Instances>()
+                            .unwrap()
+                            .current_url(),
+                    );
+                    framework
+                        .data
+                        .global
+                        .get_mut::<SyncStatus>()
+                        .unwrap()
+                        .new_videos += new_videos.len() as u32;
                     *framework.data.global.get_mut::<Message>().unwrap() =
                         Message::Success(String::from("Channel synced"));
                 }
@@ -401,38 +651,275 @@ pub fn run_single_command(
                 .priority
                 .push(Task::RenderAll);
         }
+        ["filter", "add", id, title @ ..] => {
+            // videos are filtered by the channel they are from
+            let channel = match framework
+                .data
+                .global
+                .get::<Subscriptions>()
+                .unwrap()
+                .0
+                .iter()
+                .flat_map(|item| item.videos.iter())
+                .find(|video| video.id == *id)
+            {
+                Some(video) => video.channel_id.clone(),
+                None if id.len() == 24 => id.to_string(),
+                None => {
+                    *framework.data.global.get_mut::<Message>().unwrap() = Message::Error(
+                        String::from("Not a channel ID or a video in subscriptions"),
+                    );
+                    return;
+                }
+            };
+
+            let res = (|| -> Result<FeedFilters, Box<dyn Error>> {
+                // loaded again so rules edited outside of the TUI are kept
+                let mut config = *FeedFiltersConfig::load(WriteConfig::Dont)?;
+                config.rules.push(FeedFilterRule {
+                    channel: Some(channel),
+                    title: (!title.is_empty()).then(|| title.join(" ")),
+                    ..Default::default()
+                });
+                let filters = FeedFilters::try_from(config.clone())?;
+                config.save()?;
+                Ok(filters)
+            })();
+
+            *framework.data.global.get_mut::<Message>().unwrap() = match res {
+                Ok(filters) => {
+                    framework.data.global.insert::<FeedFilters>(filters);
+                    Message::Success(String::from("Filter rule added"))
+                }
+                Err(e) => Message::Error(format!("Cannot add filter rule: {e}")),
+            };
+            framework
+                .data
+                .state
+                .get_mut::<Tasks>()
+                .unwrap()
+                .priority
+                .push(Task::Reload);
+        }
+        ["group", action @ ("add" | "remove"), id, group] => {
+            let subs = framework.data.global.get_mut::<Subscriptions>().unwrap();
+            let res = if *action == "add" {
+                subs.tag(id, group)
+            } else {
+                subs.untag(id, group)
+            }
+            .and_then(|changed| subs.save().map(|_| changed));
+
+            *framework.data.global.get_mut::<Message>().unwrap() = match res {
+                Ok(true) if *action == "add" => {
+                    Message::Success(format!("Added channel to group `{group}`"))
+                }
+                Ok(true) => Message::Success(format!("Removed channel from group `{group}`")),
+                Ok(false) if *action == "add" => {
+                    Message::Message(format!("Channel is already in group `{group}`"))
+                }
+                Ok(false) => Message::Message(format!("Channel is not in group `{group}`")),
+                Err(e) => Message::Error(e.to_string()),
+            };
+            framework
+                .data
+                .state
+                .get_mut::<Tasks>()
+                .unwrap()
+                .priority
+                .push(Task::Reload);
+        }
         ["syncall"] => {
-            *framework.data.global.get_mut::<Message>().unwrap() =
-                Message::Message(String::from("Syncing..."));
-            terminal.draw(|frame| framework.render(frame)).unwrap();
+            if !start_sync(framework, false) {
+                *framework.data.global.get_mut::<Message>().unwrap() =
+                    Message::Message(String::from("Already syncing"));
+            }
+        }
+        ["subscriptions", "import", path @ ..] if !path.is_empty() => {
+            let subs = framework.data.global.get_mut::<Subscriptions>().unwrap();
+            let res = subs
+                .import(&path.join(" "))
+                .and_then(|summary| subs.save().map(|_| summary));
+
+            *framework.data.global.get_mut::<Message>().unwrap() = match res {
+                Ok(summary) => Message::Success(summary.to_string()),
+                Err(e) => Message::Error(format!("Import failed: {e}")),
+            };
+            framework
+                .data
+                .state
+                .get_mut::<Tasks>()
+                .unwrap()
+                .priority
+                .push(Task::RenderAll);
+        }
+        ["subscriptions", "markseen"] => {
+            let subs = framework.data.global.get_mut::<Subscriptions>().unwrap();
+            let count = subs.mark_all_seen();
+            let res = subs.save();
+            framework
+                .data
+                .global
+                .get_mut::<SyncStatus>()
+                .unwrap()
+                .new_videos = 0;
+
+            *framework.data.global.get_mut::<Message>().unwrap() = match res {
+                Ok(()) => Message::Success(format!(
+                    "Marked {count} channel{} as seen",
+                    if count == 1 { "" } else { "s" }
+                )),
+                Err(e) => Message::Error(e.to_string()),
+            };
+            framework
+                .data
+                .state
+                .get_mut::<Tasks>()
+                .unwrap()
+                .priority
+                .push(Task::Reload);
+        }
+        ["subscriptions", "export", format, path @ ..] if !path.is_empty() => {
+            let format = match SubscriptionsFormat::from_name(format) {
+                Some(format) => format,
+                None => {
+                    *framework.data.global.get_mut::<Message>().unwrap() = Message::Error(format!(
+                        "Unknown format `{format}`, expected opml, csv, newpipe or invidious"
+                    ));
+                    return;
+                }
+            };
 
-            let client = framework
+            *framework.data.global.get_mut::<Message>().unwrap() = match framework
                 .data
                 .global
-                .get::<InvidiousClient>()
+                .get::<Subscriptions>()
                 .unwrap()
-                .clone();
-            let mainconfig = framework.data.global.get::<MainConfig>().unwrap();
-            let image_index = mainconfig.image_index;
-            let download_thumbnails = mainconfig.images.display();
-            let syncing = mainconfig.syncing;
+                .export(&path.join(" "), format)
+            {
+                Ok(count) => Message::Success(format!("Exported {count} channels as {format}")),
+                Err(e) => Message::Error(format!("Export failed: {e}")),
+            };
+        }
+        ["import", path @ ..] if !path.is_empty() => {
+            // taken out of the typemap to borrow all three at the same time
+            let mut history = framework.data.global.remove::<WatchHistory>().unwrap();
+            let mut library = framework.data.global.remove::<Library>().unwrap();
+            let mut localplaylists = framework.data.global.remove::<LocalPlaylists>().unwrap();
 
-            let (success, failed, empty, cached) = framework
+            let res = ImportedHistory::load(&path.join(" ")).and_then(|imported| {
+                imported.apply(&mut history, &mut library, &mut localplaylists)
+            });
+
+            framework.data.global.insert::<WatchHistory>(history);
+            framework.data.global.insert::<Library>(library);
+            framework
                 .data
                 .global
-                .get_mut::<Subscriptions>()
+                .insert::<LocalPlaylists>(localplaylists);
+
+            *framework.data.global.get_mut::<Message>().unwrap() = match res {
+                Ok(summary) => Message::Success(summary.to_string()),
+                Err(e) => Message::Error(format!("Import failed: {e}")),
+            };
+            framework
+                .data
+                .state
+                .get_mut::<Tasks>()
                 .unwrap()
-                .sync(&client, image_index, download_thumbnails, syncing);
+                .priority
+                .push(Task::RenderAll);
+        }
+        ["queue", "add", ids @ ..] if !ids.is_empty() => {
+            let mut items = Vec::new();
+            for id in ids {
+                match (|| -> Result<Item, Box<dyn Error>> {
+                    Ok(serde_json::from_str(&fs::read_to_string(
+                        home_dir()
+                            .unwrap()
+                            .join(format!(".cache/youtube-tui/info/{id}.json")),
+                    )?)?)
+                })() {
+                    // adding a playlist queues all videos in it
+                    Ok(Item::FullPlaylist(playlist)) => items.extend(playlist.videos),
+                    Ok(item) => items.push(item),
+                    Err(e) => {
+                        *framework.data.global.get_mut::<Message>().unwrap() =
+                            Message::Error(format!("Unknown item: {e}"));
+                        return;
+                    }
+                }
+            }
 
-            *framework.data.global.get_mut::<Message>().unwrap() = Message::Success(format!(
-                "Subscriptions synced: {success} success{} | {failed} fail | {cached} cached",
-                if empty != 0 {
-                    format!(" (which {empty} empty)")
-                } else {
-                    String::new()
+            let queue = framework.data.global.get_mut::<Queue>().unwrap();
+            let mut added = Vec::new();
+            for item in items {
+                let id = match item.id() {
+                    Some(id) if queue.position(id).is_none() => id.to_string(),
+                    _ => continue,
+                };
+                if queue.push(item).is_ok() {
+                    added.push(id);
                 }
-            ));
+            }
+            let _ = queue.save();
+
+            #[cfg(feature = "mpv")]
+            {
+                let provider = framework.data.global.get::<Status>().unwrap().provider;
+                let instance = framework
+                    .data
+                    .global
+                    .get::<Instances>()
+                    .unwrap()
+                    .current_url()
+                    .to_string();
+                let mainconfig = framework.data.global.get::<MainConfig>().unwrap();
+                let mpv = framework.data.global.get::<MpvWrapper>().unwrap();
+                added.iter().for_each(|id| {
+                    mpv.command(
+                        String::from("loadfile"),
+                        vec![
+                            Queue::source(id, provider, &instance, mainconfig),
+                            String::from("append-play"),
+                        ],
+                    );
+                });
+            }
 
+            *framework.data.global.get_mut::<Message>().unwrap() = if added.is_empty() {
+                Message::Message(String::from("Already in queue"))
+            } else {
+                Message::Mpv(format!(
+                    "Added {} item{} to queue",
+                    added.len(),
+                    if added.len() == 1 { "" } else { "s" }
+                ))
+            };
+        }
+        ["download", "remove", job] => {
+            let downloads = framework.data.global.get_mut::<Downloads>().unwrap();
+            let index = match job
+                .parse::<u32>()
+                .ok()
+                .and_then(|job| downloads.jobs.iter().position(|item| item.job == job))
+            {
+                Some(index) => index,
+                None => {
+                    *framework.data.global.get_mut::<Message>().unwrap() =
+                        Message::Error(format!("No download with job number `{job}`"));
+                    return;
+                }
+            };
+
+            if downloads.jobs[index].state == DownloadState::Running {
+                *framework.data.global.get_mut::<Message>().unwrap() =
+                    Message::Error(String::from("Cannot remove a running download"));
+                return;
+            }
+
+            downloads.jobs.remove(index);
+            let _ = downloads.save();
             framework
                 .data
                 .state
@@ -441,6 +928,551 @@ pub fn run_single_command(
                 .priority
                 .push(Task::RenderAll);
         }
+        ["library", "verify"] => {
+            let res = rescan_library(framework);
+            *framework.data.global.get_mut::<Message>().unwrap() = match res {
+                Ok(report) if report.is_clean() => {
+                    Message::Success(format!("Library is all good: {report}"))
+                }
+                Ok(report) => {
+                    // only partial files the user has seen can be deleted by `library verify fix`
+                    framework
+                        .data
+                        .global
+                        .get_mut::<OfflineIndex>()
+                        .unwrap()
+                        .listed_partial = report.partial.clone();
+                    Message::Message(format!(
+                        "{report} (run `library verify fix` to remove missing items from index and delete the partial files listed)"
+                    ))
+                }
+                Err(e) => Message::Error(e.to_string()),
+            };
+        }
+        ["library", "verify", "fix"] => {
+            let res = rescan_library(framework).and_then(|report| {
+                let index = framework.data.global.get_mut::<OfflineIndex>().unwrap();
+                let deleted = index.fix(&report)?;
+                index.save()?;
+                Ok((report, deleted))
+            });
+
+            *framework.data.global.get_mut::<Message>().unwrap() = match res {
+                Ok((report, _)) if report.is_clean() => {
+                    Message::Success(format!("Library is all good: {report}"))
+                }
+                Ok((report, deleted)) => Message::Success(format!(
+                    "Removed {} missing items from index (they are still in library), deleted {deleted} of {} partial files{}",
+                    report.missing.len(),
+                    report.partial.len(),
+                    if deleted < report.partial.len() {
+                        ", run `library verify` to list the rest before deleting them"
+                    } else {
+                        ""
+                    }
+                )),
+                Err(e) => Message::Error(e.to_string()),
+            };
+        }
+        ["library", "delete", id] => {
+            let res = rescan_library(framework).and_then(|_| {
+                let save_path = save_path(framework.data.global.get::<MainConfig>().unwrap())
+                    .ok_or("`save-path` is not set in main config")?;
+                let index = framework.data.global.get_mut::<OfflineIndex>().unwrap();
+                index.delete(id, &save_path)?;
+                index.save()
+            });
+
+            *framework.data.global.get_mut::<Message>().unwrap() = match res {
+                Ok(()) => Message::Success(String::from("Saved file deleted")),
+                Err(e) => Message::Error(e.to_string()),
+            };
+        }
+        ["download", action @ ("pause" | "resume" | "cancel"), job] => {
+            let job = match job.parse::<u32>() {
+                Ok(job) => job,
+                Err(_) => {
+                    *framework.data.global.get_mut::<Message>().unwrap() =
+                        Message::Error(format!("Invalid job number `{job}`"));
+                    return;
+                }
+            };
+
+            let mut downloads = framework.data.global.remove::<Downloads>().unwrap();
+            let res = match *action {
+                "pause" => downloads.pause(job),
+                "resume" => downloads.resume(job),
+                _ => downloads.cancel(job),
+            };
+            if res.is_ok() {
+                downloads.start_queued(framework.data.global.get::<MainConfig>().unwrap());
+                let _ = downloads.save();
+            }
+            framework.data.global.insert::<Downloads>(downloads);
+
+            *framework.data.global.get_mut::<Message>().unwrap() = match res {
+                Ok(()) => Message::Success(format!(
+                    "{} download #{job}",
+                    match *action {
+                        "pause" => "Paused",
+                        "resume" => "Resumed",
+                        _ => "Cancelled",
+                    }
+                )),
+                Err(e) => Message::Error(e.to_string()),
+            };
+            framework
+                .data
+                .state
+                .get_mut::<Tasks>()
+                .unwrap()
+                .priority
+                .push(Task::RenderAll);
+        }
+        ["download", "clear"] => {
+            let downloads = framework.data.global.get_mut::<Downloads>().unwrap();
+            downloads.clear();
+            let _ = downloads.save();
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Success(String::from("Cleared finished downloads"));
+            framework
+                .data
+                .state
+                .get_mut::<Tasks>()
+                .unwrap()
+                .priority
+                .push(Task::RenderAll);
+        }
+        ["download", "audio", id] | ["download", "video", id] | ["download", id] => {
+            let item = match (|| -> Result<Item, Box<dyn Error>> {
+                Ok(serde_json::from_str(&fs::read_to_string(
+                    home_dir()
+                        .unwrap()
+                        .join(format!(".cache/youtube-tui/info/{id}.json")),
+                )?)?)
+            })() {
+                Ok(item) => item,
+                Err(e) => {
+                    *framework.data.global.get_mut::<Message>().unwrap() =
+                        Message::Error(format!("Unknown item: {e}"));
+                    return;
+                }
+            };
+
+            let provider = framework.data.global.get::<Status>().unwrap().provider;
+            let instance = framework
+                .data
+                .global
+                .get::<Instances>()
+                .unwrap()
+                .current_url()
+                .to_string();
+            let urls = match &item {
+                Item::FullPlaylist(playlist) => playlist
+                    .videos
+                    .iter()
+                    .filter_map(|video| Some(Queue::url(video.id()?, provider, &instance)))
+                    .collect(),
+                _ => vec![Queue::url(id, provider, &instance)],
+            };
+
+            let mut downloads = framework.data.global.remove::<Downloads>().unwrap();
+            if downloads.jobs.iter().any(|job| {
+                job.id == *id
+                    && matches!(
+                        job.state,
+                        DownloadState::Queued | DownloadState::Running | DownloadState::Paused
+                    )
+            }) {
+                framework.data.global.insert::<Downloads>(downloads);
+                *framework.data.global.get_mut::<Message>().unwrap() =
+                    Message::Message(String::from("Already downloading"));
+                return;
+            }
+
+            let title = item.to_string();
+            let job = downloads.push(item, urls, command[1] == "audio");
+            let started =
+                downloads.start_queued(framework.data.global.get::<MainConfig>().unwrap());
+            let _ = downloads.save();
+            framework.data.global.insert::<Downloads>(downloads);
+
+            *framework.data.global.get_mut::<Message>().unwrap() = if started {
+                Message::Success(format!("Downloading {title}"))
+            } else {
+                Message::Success(format!("Queued {title} as job #{job}"))
+            };
+        }
+        ["queue", "remove", identifier] => {
+            let queue = framework.data.global.get_mut::<Queue>().unwrap();
+            // an identifier can either be the position in queue (starting from 1) or the video id
+            let index = match identifier.parse::<usize>() {
+                Ok(position) if position != 0 && position <= queue.0.len() => Some(position - 1),
+                _ => queue.position(identifier),
+            };
+
+            let index = if let Some(index) = index {
+                index
+            } else {
+                *framework.data.global.get_mut::<Message>().unwrap() =
+                    Message::Error(String::from("No item with that ID found in queue"));
+                return;
+            };
+
+            let item = queue.0.remove(index);
+            let _ = queue.save();
+
+            #[cfg(feature = "mpv")]
+            if let Some(id) = item.id() {
+                let mpv = framework.data.global.get::<MpvWrapper>().unwrap();
+                if let Some(index) = Queue::mpv_index(mpv, id) {
+                    mpv.command(String::from("playlist-remove"), vec![index.to_string()]);
+                }
+            }
+            #[cfg(not(feature = "mpv"))]
+            let _ = item;
+
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Success(String::from("Removed from queue"));
+        }
+        ["queue", "move", from, to] => {
+            let (from, to) = match (from.parse::<usize>(), to.parse::<usize>()) {
+                (Ok(from), Ok(to)) if from != 0 && to != 0 => (from - 1, to - 1),
+                _ => {
+                    *framework.data.global.get_mut::<Message>().unwrap() = Message::Message(
+                        String::from("Usage: `queue move {from position} {to position}`"),
+                    );
+                    return;
+                }
+            };
+
+            let queue = framework.data.global.get_mut::<Queue>().unwrap();
+            if !queue.r#move(from, to) {
+                *framework.data.global.get_mut::<Message>().unwrap() =
+                    Message::Error(String::from("Position out of range"));
+                return;
+            }
+            let _ = queue.save();
+
+            // mpv moves the entry to *before* the target entry, so it is moved before the item
+            // that now follows it in queue, or to the end
+            #[cfg(feature = "mpv")]
+            {
+                let queue = framework.data.global.get::<Queue>().unwrap();
+                let mpv = framework.data.global.get::<MpvWrapper>().unwrap();
+                let moved = queue.0[to].id().and_then(|id| Queue::mpv_index(mpv, id));
+                let target = match queue.0.get(to + 1) {
+                    Some(next) => next.id().and_then(|id| Queue::mpv_index(mpv, id)),
+                    None => mpv
+                        .property("playlist-count".to_string())
+                        .and_then(|count| count.parse::<usize>().ok()),
+                };
+
+                if let (Some(moved), Some(target)) = (moved, target) {
+                    mpv.command(
+                        String::from("playlist-move"),
+                        vec![moved.to_string(), target.to_string()],
+                    );
+                }
+            }
+
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Success(String::from("Queue reordered"));
+        }
+        ["queue", "clear"] => {
+            let queue = framework.data.global.get_mut::<Queue>().unwrap();
+            queue.0.clear();
+            let _ = queue.save();
+
+            // `stop` also clears the mpv playlist
+            #[cfg(feature = "mpv")]
+            framework
+                .data
+                .global
+                .get::<MpvWrapper>()
+                .unwrap()
+                .command(String::from("stop"), Vec::new());
+
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Success(String::from("Queue cleared"));
+        }
+        ["queue", "shuffle"] => {
+            #[cfg(feature = "mpv")]
+            let playing = framework
+                .data
+                .global
+                .get::<Queue>()
+                .unwrap()
+                .playing(framework.data.global.get::<MpvWrapper>().unwrap());
+            #[cfg(not(feature = "mpv"))]
+            let playing = None;
+
+            let queue = framework.data.global.get_mut::<Queue>().unwrap();
+            queue.shuffle(playing);
+            let _ = queue.save();
+
+            // the currently playing item is kept in the mpv playlist, the rest are reloaded in
+            // the new order
+            #[cfg(feature = "mpv")]
+            {
+                let provider = framework.data.global.get::<Status>().unwrap().provider;
+                let instance = framework
+                    .data
+                    .global
+                    .get::<Instances>()
+                    .unwrap()
+                    .current_url()
+                    .to_string();
+                let mainconfig = framework.data.global.get::<MainConfig>().unwrap();
+                let mpv = framework.data.global.get::<MpvWrapper>().unwrap();
+                let queue = framework.data.global.get::<Queue>().unwrap();
+                if playing.is_some() {
+                    mpv.command(String::from("playlist-clear"), Vec::new());
+                    queue.load_into(mpv, 1, provider, &instance, mainconfig);
+                } else {
+                    mpv.command(String::from("stop"), Vec::new());
+                    queue.load_into(mpv, 0, provider, &instance, mainconfig);
+                }
+            }
+
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Success(String::from("Queue shuffled"));
+        }
+        #[cfg(feature = "mpv")]
+        ["queue", "play", ..] => {
+            let queue = framework.data.global.get::<Queue>().unwrap();
+            let index = match command.get(2) {
+                None => Some(0),
+                Some(identifier) => match identifier.parse::<usize>() {
+                    Ok(position) if position != 0 && position <= queue.0.len() => {
+                        Some(position - 1)
+                    }
+                    _ => queue.position(identifier),
+                },
+            };
+
+            let index = match index {
+                Some(index) if index < queue.0.len() => index,
+                _ => {
+                    *framework.data.global.get_mut::<Message>().unwrap() =
+                        Message::Error(String::from("Nothing to play in queue"));
+                    return;
+                }
+            };
+
+            let provider = framework.data.global.get::<Status>().unwrap().provider;
+            let instance = framework
+                .data
+                .global
+                .get::<Instances>()
+                .unwrap()
+                .current_url()
+                .to_string();
+            let mainconfig = framework.data.global.get::<MainConfig>().unwrap();
+            let mpv = framework.data.global.get::<MpvWrapper>().unwrap();
+            mpv.command(String::from("stop"), Vec::new());
+            queue.load_into(mpv, 0, provider, &instance, mainconfig);
+            mpv.command(String::from("playlist-play-index"), vec![index.to_string()]);
+            mpv.set_property(String::from("pause"), String::from("no"));
+
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Mpv(String::from("Playing queue"));
+        }
+        #[cfg(not(feature = "mpv"))]
+        ["queue", "play", ..] => {
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Error(String::from("Feature `mpv` is disabled and not compiled"));
+        }
+        ["playlist", action @ ("create" | "delete"), name] => {
+            let playlists = framework.data.global.get_mut::<LocalPlaylists>().unwrap();
+            let res = if *action == "create" {
+                playlists.create(name)
+            } else {
+                playlists.delete(name)
+            };
+            let _ = playlists.save();
+
+            *framework.data.global.get_mut::<Message>().unwrap() = match res {
+                Ok(()) if *action == "create" => {
+                    Message::Success(format!("Created playlist `{name}`"))
+                }
+                Ok(()) => Message::Success(format!("Deleted playlist `{name}`")),
+                Err(e) => Message::Error(e.to_string()),
+            };
+        }
+        ["playlist", "rename", name, new_name] => {
+            let playlists = framework.data.global.get_mut::<LocalPlaylists>().unwrap();
+            let res = playlists.rename(name, new_name);
+            let _ = playlists.save();
+
+            *framework.data.global.get_mut::<Message>().unwrap() = match res {
+                Ok(()) => Message::Success(format!("Renamed playlist to `{new_name}`")),
+                Err(e) => Message::Error(e.to_string()),
+            };
+        }
+        ["playlist", "add", name, ids @ ..] if !ids.is_empty() => {
+            let mut items = Vec::new();
+            for id in ids {
+                match (|| -> Result<Item, Box<dyn Error>> {
+                    Ok(serde_json::from_str(&fs::read_to_string(
+                        home_dir()
+                            .unwrap()
+                            .join(format!(".cache/youtube-tui/info/{id}.json")),
+                    )?)?)
+                })() {
+                    // adding a playlist adds all videos in it
+                    Ok(Item::FullPlaylist(playlist)) => items.extend(playlist.videos),
+                    Ok(item) => items.push(item),
+                    Err(e) => {
+                        *framework.data.global.get_mut::<Message>().unwrap() =
+                            Message::Error(format!("Unknown item: {e}"));
+                        return;
+                    }
+                }
+            }
+
+            let playlists = framework.data.global.get_mut::<LocalPlaylists>().unwrap();
+            let playlist = match playlists.get_mut(name) {
+                Some(playlist) => playlist,
+                None => {
+                    *framework.data.global.get_mut::<Message>().unwrap() =
+                        Message::Error(format!("Playlist `{name}` does not exist"));
+                    return;
+                }
+            };
+            let added = playlist.add(items);
+            let _ = playlists.save();
+
+            *framework.data.global.get_mut::<Message>().unwrap() = if added == 0 {
+                Message::Message(format!("Already in playlist `{name}`"))
+            } else {
+                Message::Success(format!(
+                    "Added {added} item{} to playlist `{name}`",
+                    if added == 1 { "" } else { "s" }
+                ))
+            };
+        }
+        ["playlist", "remove", name, identifier] => {
+            let playlists = framework.data.global.get_mut::<LocalPlaylists>().unwrap();
+            let res = match playlists.get_mut(name) {
+                Some(playlist) => match playlist.position(identifier) {
+                    Some(index) => {
+                        playlist.items.remove(index);
+                        Ok(())
+                    }
+                    None => Err(String::from("No item with that ID found in playlist")),
+                },
+                None => Err(format!("Playlist `{name}` does not exist")),
+            };
+            let _ = playlists.save();
+
+            *framework.data.global.get_mut::<Message>().unwrap() = match res {
+                Ok(()) => Message::Success(String::from("Removed from playlist")),
+                Err(e) => Message::Error(e),
+            };
+        }
+        // `to` can also be `up` or `down` to move the item by one position
+        ["playlist", "move", name, from, to] => {
+            let playlists = framework.data.global.get_mut::<LocalPlaylists>().unwrap();
+            let playlist = match playlists.get_mut(name) {
+                Some(playlist) => playlist,
+                None => {
+                    *framework.data.global.get_mut::<Message>().unwrap() =
+                        Message::Error(format!("Playlist `{name}` does not exist"));
+                    return;
+                }
+            };
+
+            let moved = playlist.position(from).and_then(|from| {
+                let to = match *to {
+                    "up" => from.checked_sub(1)?,
+                    "down" => from + 1,
+                    to => to.parse::<usize>().ok()?.checked_sub(1)?,
+                };
+                Some(playlist.r#move(from, to))
+            });
+            let _ = playlists.save();
+
+            *framework.data.global.get_mut::<Message>().unwrap() = match moved {
+                Some(true) => Message::Success(String::from("Playlist reordered")),
+                Some(false) => Message::Error(String::from("Position out of range")),
+                None => Message::Message(String::from(
+                    "Usage: `playlist move {name} {position or id} {position/up/down}`",
+                )),
+            };
+        }
+        #[cfg(feature = "mpv")]
+        ["playlist", "play", name, ..] => {
+            let (items, offset) = match framework
+                .data
+                .global
+                .get::<LocalPlaylists>()
+                .unwrap()
+                .get(name)
+            {
+                Some(playlist) if !playlist.items.is_empty() => {
+                    match command
+                        .get(3)
+                        .map(|identifier| playlist.position(identifier))
+                    {
+                        None => (playlist.items.clone(), 0),
+                        Some(Some(offset)) => (playlist.items.clone(), offset),
+                        Some(None) => {
+                            *framework.data.global.get_mut::<Message>().unwrap() =
+                                Message::Error(format!("Item not found in playlist `{name}`"));
+                            return;
+                        }
+                    }
+                }
+                Some(_) => {
+                    *framework.data.global.get_mut::<Message>().unwrap() =
+                        Message::Error(format!("Playlist `{name}` is empty"));
+                    return;
+                }
+                None => {
+                    *framework.data.global.get_mut::<Message>().unwrap() =
+                        Message::Error(format!("Playlist `{name}` does not exist"));
+                    return;
+                }
+            };
+
+            // the playlist is appended to the queue, items already in the queue are moved to the
+            // end so the playlist stays in order
+            let queue = framework.data.global.get_mut::<Queue>().unwrap();
+            let len = items.len();
+            items.into_iter().for_each(|item| {
+                let _ = queue.push(item);
+            });
+            let _ = queue.save();
+
+            // `queue play` takes positions starting from 1
+            let position = (queue.0.len() - len + offset + 1).to_string();
+            run_single_command(&["queue", "play", &position], framework, terminal);
+        }
+        #[cfg(not(feature = "mpv"))]
+        ["playlist", "play", ..] => {
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Error(String::from("Feature `mpv` is disabled and not compiled"));
+        }
+        ["playlist", "export", name] => {
+            let provider = framework.data.global.get::<Status>().unwrap().provider;
+            let mainconfig = framework.data.global.get::<MainConfig>().unwrap();
+            let res = match framework
+                .data
+                .global
+                .get::<LocalPlaylists>()
+                .unwrap()
+                .get(name)
+            {
+                Some(playlist) => playlist.export(provider, &mainconfig.invidious_instance),
+                None => Err(format!("Playlist `{name}` does not exist").into()),
+            };
+
+            *framework.data.global.get_mut::<Message>().unwrap() = match res {
+                Ok(path) => Message::Success(format!("Playlist saved to {}", path.display())),
+                Err(e) => Message::Error(e.to_string()),
+            };
+        }
         ["key", keycode, modifier] => {
             let (keycodeserde, modifier) =
                 match (|| -> Result<(KeyCodeSerde, u8), Box<dyn Error>> {
@@ -465,6 +1497,79 @@ pub fn run_single_command(
             key_input(KeyEvent::new(keycode, keymodifier), framework, terminal)
         }
         #[cfg(feature = "mpv")]
+        ["mpv", "chapter", index] => {
+            let index = match index.parse::<usize>() {
+                Ok(index) if index != 0 => index - 1,
+                _ => {
+                    *framework.data.global.get_mut::<Message>().unwrap() =
+                        Message::Error(String::from("Chapter should be a number starting from 1"));
+                    return;
+                }
+            };
+
+            // chapters of the video in the current page
+            let chapter = env::var("id").ok().and_then(|id| {
+                match serde_json::from_str::<Item>(
+                    &fs::read_to_string(
+                        home_dir()
+                            .unwrap()
+                            .join(format!(".cache/youtube-tui/info/{id}.json")),
+                    )
+                    .ok()?,
+                )
+                .ok()?
+                {
+                    Item::FullVideo(video) => Some((video.id, video.chapters.get(index)?.clone())),
+                    _ => None,
+                }
+            });
+
+            // if not in a video page, jump to the chapter in whatever is playing
+            let (id, chapter) = if let Some(chapter) = chapter {
+                chapter
+            } else {
+                let res = framework
+                    .data
+                    .global
+                    .get::<MpvWrapper>()
+                    .unwrap()
+                    .set_property(String::from("chapter"), index.to_string());
+                *framework.data.global.get_mut::<Message>().unwrap() = match res {
+                    MpvResponse::Copy => Message::Mpv(format!("Jumped to chapter {}", index + 1)),
+                    MpvResponse::Error(e) => Message::Error(format!("MPV error: {e}")),
+                    _ => unreachable!(),
+                };
+                return;
+            };
+
+            play_from(framework, id, chapter.start as f64);
+            *framework.data.global.get_mut::<Message>().unwrap() = Message::Mpv(format!(
+                "Playing from chapter {}: {}",
+                index + 1,
+                chapter.title
+            ));
+        }
+        #[cfg(feature = "mpv")]
+        ["mpv", "playfrom", secs] => {
+            let (id, secs) = match (env::var("id"), secs.parse::<f64>()) {
+                (Ok(id), Ok(secs)) => (id, secs),
+                (Err(_), _) => {
+                    *framework.data.global.get_mut::<Message>().unwrap() =
+                        Message::Error(String::from("Not in a video page"));
+                    return;
+                }
+                (_, Err(_)) => {
+                    *framework.data.global.get_mut::<Message>().unwrap() =
+                        Message::Error(String::from("Time should be a number in seconds"));
+                    return;
+                }
+            };
+
+            play_from(framework, id, secs);
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                Message::Mpv(format!("Playing from {}", secs_display_string(secs as u32)));
+        }
+        #[cfg(feature = "mpv")]
         ["mpv", "prop", property] => {
             let res = framework
                 .data
@@ -531,6 +1636,104 @@ pub fn run_single_command(
             *framework.data.global.get_mut::<Message>().unwrap() =
                 Message::Error(String::from("Feature `mpv` is disabled and not compiled"));
         }
+        ["captions", "tracks"] => {
+            let message = match page_captions(framework) {
+                Ok(captions) if captions.tracks.is_empty() => {
+                    Message::Message(String::from("No captions avaliable"))
+                }
+                Ok(captions) => Message::Message(format!(
+                    "Captions: {}",
+                    captions
+                        .tracks
+                        .iter()
+                        .map(|track| format!("{} ({})", track.label, track.language_code))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )),
+                Err(e) => Message::Error(format!("Cannot load captions: {e}")),
+            };
+            *framework.data.global.get_mut::<Message>().unwrap() = message;
+        }
+        ["captions", "load", ..] => {
+            let name = command[2..].join(" ");
+            let instance = framework
+                .data
+                .global
+                .get::<Instances>()
+                .unwrap()
+                .current_url()
+                .to_string();
+            let res = page_captions(framework).and_then(|captions| {
+                let index = captions
+                    .find_track(&name)
+                    .ok_or(format!("No captions in `{name}`"))?;
+                captions.load_track(&instance, index)?;
+                Ok(captions.tracks[index].label.clone())
+            });
+            *framework.data.global.get_mut::<Message>().unwrap() = match res {
+                Ok(label) => Message::Success(format!("Loaded captions: {label}")),
+                Err(e) => Message::Error(format!("Cannot load captions: {e}")),
+            };
+        }
+        ["captions", "search", ..] => {
+            let captions = framework.data.global.get_mut::<Captions>().unwrap();
+            captions.query = Some(command[2..].join(" "));
+            let found = captions.search(true);
+            *framework.data.global.get_mut::<Message>().unwrap() = match found {
+                Some(_) => Message::None,
+                None => Message::Error(format!(
+                    "Not found in captions: `{}`",
+                    command[2..].join(" ")
+                )),
+            };
+        }
+        ["captions", direction @ ("next" | "prev")] => {
+            let captions = framework.data.global.get_mut::<Captions>().unwrap();
+            if captions.query.is_none() {
+                *framework.data.global.get_mut::<Message>().unwrap() =
+                    Message::Error(String::from("Nothing was searched"));
+                return;
+            }
+
+            if captions.search(*direction == "next").is_none() {
+                *framework.data.global.get_mut::<Message>().unwrap() =
+                    Message::Error(String::from("Not found in captions"));
+            }
+        }
+        ["captions", "export", format] => {
+            let format = match CaptionFormat::from_extension(format) {
+                Some(format) => format,
+                None => {
+                    *framework.data.global.get_mut::<Message>().unwrap() =
+                        Message::Error(String::from("Format should be one of srt, vtt or txt"));
+                    return;
+                }
+            };
+
+            let res = framework
+                .data
+                .global
+                .get::<Captions>()
+                .unwrap()
+                .export(format);
+            *framework.data.global.get_mut::<Message>().unwrap() = match res {
+                Ok(path) => Message::Success(format!("Captions saved to {}", path.display())),
+                Err(e) => Message::Error(format!("Cannot export captions: {e}")),
+            };
+        }
+        ["prompt", ..] => {
+            let status = framework.data.global.get_mut::<Status>().unwrap();
+            status.reset_command_capture();
+            let textfield = status.command_capture.as_mut().unwrap();
+            // same as pasting, the width is not known yet
+            textfield.set_width(u16::MAX);
+            format!("{} ", command[1..].join(" "))
+                .trim_start()
+                .chars()
+                .for_each(|c| {
+                    let _ = textfield.push(c);
+                });
+        }
         ["echo", r#type, ..] => {
             *framework.data.global.get_mut::<Message>().unwrap() = match *r#type {
                 "message" => Message::Message(command[2..].join(" ")),
@@ -559,6 +1762,57 @@ pub fn run_single_command(
     }
 }
 
+/// play the video from a position in the embedded player, seek to it if it is already playing
+#[cfg(feature = "mpv")]
+fn play_from(framework: &mut Framework, id: String, start: f64) {
+    let provider = framework.data.global.get::<Status>().unwrap().provider;
+    let instance = framework
+        .data
+        .global
+        .get::<Instances>()
+        .unwrap()
+        .current_url()
+        .to_string();
+    let positions = framework.data.global.get_mut::<Positions>().unwrap();
+    let playing = positions.current.as_ref() == Some(&id);
+    // the video is not loaded yet, so seek to the position once it is loaded
+    if !playing {
+        positions.start = Some((id.clone(), start));
+    }
+
+    let mpv = framework.data.global.get::<MpvWrapper>().unwrap();
+    if playing {
+        mpv.command(
+            String::from("seek"),
+            vec![start.to_string(), String::from("absolute")],
+        );
+    } else {
+        mpv.command(
+            String::from("loadfile"),
+            vec![Queue::url(&id, provider, &instance)],
+        );
+    }
+    mpv.set_property(String::from("pause"), String::from("no"));
+}
+
+/// captions of the video in the current page, caption tracks are fetched if the video is not
+/// the one already loaded
+fn page_captions(framework: &mut Framework) -> Result<&mut Captions, Box<dyn Error>> {
+    let id = env::var("id").map_err(|_| "Not in a video page")?;
+    let instance = framework
+        .data
+        .global
+        .get::<Instances>()
+        .unwrap()
+        .current_url()
+        .to_string();
+    let captions = framework.data.global.get_mut::<Captions>().unwrap();
+    if captions.id.as_ref() != Some(&id) {
+        captions.fetch_tracks(&instance, &id)?;
+    }
+    Ok(captions)
+}
+
 const HELLO_WORLDS: &[&str] = &[
     "printf(\"Hello World\")",
     "std::cout << \"Hello World\"",
@@ -590,6 +1844,60 @@ const HELLO_WORLDS: &[&str] = &[
     "<h1>Hello World<\\h1>",
 ];
 
+/// `cache stats/clear/prune`, used by both the TUI and text commands
+fn cache_command(
+    args: &[&str],
+    cache: &ResponseCache,
+    config: &CacheConfig,
+) -> Result<String, String> {
+    match args {
+        ["stats"] => Ok(cache.stats(config).to_string()),
+        ["prune"] => Ok(format!(
+            "Removed {} expired, least recently used or unindexed responses",
+            cache.prune(config)
+        )),
+        ["clear"] => Ok(format!("Removed {} cached responses", cache.clear(None))),
+        ["clear", kind, key @ ..] => {
+            let kind = CacheKind::from_name(kind).ok_or_else(|| {
+                format!(
+                    "Unknown kind `{kind}`, expected video, channel, playlist, search or trending"
+                )
+            })?;
+            if key.is_empty() {
+                Ok(format!(
+                    "Removed {} cached {kind} responses",
+                    cache.clear(Some(kind))
+                ))
+            } else if cache.remove(kind, &key.join(" ")) {
+                Ok(format!("Removed cached {kind} response"))
+            } else {
+                Err(format!("No cached {kind} response for `{}`", key.join(" ")))
+            }
+        }
+        _ => Err(String::from(
+            "Unknown cache command, expected stats, clear or prune",
+        )),
+    }
+}
+
+/// update the offline index with files in `save-path`
+fn rescan_library(framework: &mut Framework) -> Result<VerifyReport, Box<dyn Error>> {
+    let mut index = framework.data.global.remove::<OfflineIndex>().unwrap();
+    let res = (|| -> Result<VerifyReport, Box<dyn Error>> {
+        let save_path = save_path(framework.data.global.get::<MainConfig>().unwrap())
+            .ok_or("`save-path` is not set in main config")?;
+        let report = index.scan(
+            &save_path,
+            framework.data.global.get::<Library>().unwrap(),
+            framework.data.global.get::<Downloads>().unwrap(),
+        )?;
+        index.save()?;
+        Ok(report)
+    })();
+    framework.data.global.insert::<OfflineIndex>(index);
+    res
+}
+
 fn help_msg(cmdefines: &CommandsRemapConfig) -> String {
     format!("\x1b[32mYouTube TUI commands\x1b[0m
 
@@ -601,6 +1909,9 @@ fn help_msg(cmdefines: &CommandsRemapConfig) -> String {
 \x1b[91mINFORMATIONAL:\x1b[0m
     \x1b[33mhelp\x1b[0m                            Display this message
     \x1b[33mversion\x1b[0m                         Print version info and exit
+    \x1b[33msync\x1b[0m                            Sync all subscriptions without launching the TUI
+    \x1b[33minstance discover [region]\x1b[0m      Find a healthy public instance and save it to main config
+    \x1b[33mcache [stats/clear/prune]\x1b[0m       Manage cached responses without launching the TUI
 
 \x1b[91mLOADPAGE:
 \x1b[37mloadpage [page] loads the corresponding page\x1b[0m
@@ -608,13 +1919,18 @@ fn help_msg(cmdefines: &CommandsRemapConfig) -> String {
     \x1b[33mloadpage trending\x1b[0m               Loads the trending videos page
     \x1b[33mloadpage watchhistory\x1b[0m           Loads the watch history page
     \x1b[33mloadpage subscriptions\x1b[0m          Loads the subscriptions page
+    \x1b[33mloadpage timeline [page]\x1b[0m        Loads videos from all subscriptions in a single list, newest first
     \x1b[33mloadpage bookmarks\x1b[0m              Loads the bookmarks page
     \x1b[33mloadpage library\x1b[0m                Loads the library (saved items) page
-    \x1b[33mloadpage feed\x1b[0m                   Loads the library (feed) page
+    \x1b[33mloadpage feed [group]\x1b[0m           Loads the library (feed) page, only showing channels in the group if given
     \x1b[33mloadpage search [query]\x1b[0m         Loads the search page with the given query
     \x1b[33mloadpage video [identifier]\x1b[0m     Loads the video item page
     \x1b[33mloadpage playlist [identifier]\x1b[0m  Loads the playlist item page
     \x1b[33mloadpage channel [identifier]\x1b[0m   Loads the channel item page
+    \x1b[33mloadpage queue\x1b[0m                  Loads the play queue page
+    \x1b[33mloadpage nowplaying\x1b[0m             Loads the player page
+    \x1b[33mloadpage downloads\x1b[0m              Loads the downloads page
+    \x1b[33mloadpage playlists [name]\x1b[0m       Loads the local playlists page, or a local playlist
 
 \x1b[91mHISTORY:\x1b[0m
     \x1b[33mhistory back\x1b[0m                    Revert back to previous state
@@ -629,8 +1945,22 @@ fn help_msg(cmdefines: &CommandsRemapConfig) -> String {
     \x1b[33mparrun [command]\x1b[0m                Runs a system command non blocking (e.g. `run firefox example.com`)
     \x1b[33mcopy [text]\x1b[0m                     Copies text to clipboard
     \x1b[33mkey [keycode] [keymodifier]\x1b[0m     Create a key input event
+    \x1b[33mprompt [text]\x1b[0m                   Open command line with text already entered
     \x1b[33mecho [mode] [message]\x1b[0m           Dispalys a message in message bar, mode: none, success, warn, error, mpv (can be overwritten by mpv player)
 
+\x1b[91mINSTANCES:\x1b[0m
+    \x1b[33minstance list\x1b[0m                   Show all invidious instances and their health
+    \x1b[33minstance test\x1b[0m                   Check the health of all instances now
+    \x1b[33minstance switch [position or url]\x1b[0m
+                                    Use another instance until the TUI exits
+    \x1b[33minstance discover [region]\x1b[0m      Find the fastest healthy public instance, and save it and the next fastest as fallbacks to main config
+
+\x1b[91mCACHE:\x1b[0m
+    \x1b[33mcache stats\x1b[0m                     Show the number and size of cached responses
+    \x1b[33mcache clear [kind] [key]\x1b[0m        Remove all cached responses, responses of a kind, or a single response
+    \x1b[33mcache prune\x1b[0m                     Remove expired responses, least recently used responses if the cache is full, and files not in the cache index
+    \x1b[33moffline [on/off]\x1b[0m                Toggle offline mode, where pages are only loaded from cache
+
 \x1b[91mLIBRARY:\x1b[0m
     \x1b[33mbookmark [id]\x1b[0m                   Bookmark item with ID (item must be already loaded)
     \x1b[33munmark [id]\x1b[0m                     Remove bookmark item with ID
@@ -638,11 +1968,60 @@ fn help_msg(cmdefines: &CommandsRemapConfig) -> String {
     \x1b[33msub/sync [id or url]\x1b[0m            Add channel to subscription, or sync an existing channel
     \x1b[33munsub [id or url]\x1b[0m               Remove channel from subscription
     \x1b[33msyncall\x1b[0m                         Sync all subscriptions
+    \x1b[33mfilter add [id] [title regex]\x1b[0m   Hide videos from a channel (or the channel of a video) in the feed, optionally only titles matching the regex
+    \x1b[33msubscriptions markseen\x1b[0m          Clear the new videos marker of all subscriptions
+    \x1b[33mgroup add [id] [group]\x1b[0m          Add a subscribed channel to a group
+    \x1b[33mgroup remove [id] [group]\x1b[0m       Remove a subscribed channel from a group
+    \x1b[33msubscriptions import [file]\x1b[0m     Import subscriptions from an OPML, Google Takeout CSV, NewPipe or Invidious file
+    \x1b[33msubscriptions export [format] [file]\x1b[0m
+                                    Export subscriptions, format: opml, csv, newpipe, invidious
+    \x1b[33mimport [file]\x1b[0m                   Import watch history and playlists from a NewPipe or FreeTube backup
+
+\x1b[91mOFFLINE LIBRARY:\x1b[0m
+    \x1b[33mlibrary verify\x1b[0m                  Check for missing, orphaned and partly downloaded files in save path
+    \x1b[33mlibrary verify fix\x1b[0m              Remove missing items from index, delete partial files listed by `library verify`
+    \x1b[33mlibrary delete [id]\x1b[0m             Delete the saved file of an item
+
+\x1b[91mQUEUE:\x1b[0m
+    \x1b[33mqueue add [id...]\x1b[0m               Add videos (or all videos in a playlist) to the play queue
+    \x1b[33mqueue remove [position or id]\x1b[0m   Remove an item from queue
+    \x1b[33mqueue move [from] [to]\x1b[0m          Move an item in queue to another position
+    \x1b[33mqueue clear\x1b[0m                     Remove all items from queue and stop the player
+    \x1b[33mqueue shuffle\x1b[0m                   Shuffle the queue, keeping the playing item
+    \x1b[33mqueue play [position or id]\x1b[0m     Start playing the queue from an item
+
+\x1b[91mLOCAL PLAYLISTS:\x1b[0m
+    \x1b[33mplaylist create [name]\x1b[0m          Create an empty playlist, names cannot contain spaces
+    \x1b[33mplaylist rename [name] [new]\x1b[0m    Rename a playlist
+    \x1b[33mplaylist delete [name]\x1b[0m          Delete a playlist
+    \x1b[33mplaylist add [name] [id...]\x1b[0m     Add videos (or all videos in a playlist) to a playlist
+    \x1b[33mplaylist remove [name] [id]\x1b[0m     Remove an item by its position or id
+    \x1b[33mplaylist move [name] [id] [to]\x1b[0m  Move an item to a position, or `up`/`down` by one
+    \x1b[33mplaylist play [name] [id]\x1b[0m       Add the playlist to the queue and play it
+    \x1b[33mplaylist export [name]\x1b[0m          Save the playlist as an M3U file
+
+\x1b[91mCAPTIONS:\x1b[0m
+    \x1b[33mcaptions tracks\x1b[0m                 List avaliable captions of the video in page
+    \x1b[33mcaptions load [language]\x1b[0m        Load captions by language code or label
+    \x1b[33mcaptions search [text]\x1b[0m          Jump to the next line containing the text in transcript
+    \x1b[33mcaptions next/prev\x1b[0m              Jump to the next/previous search result
+    \x1b[33mcaptions export [format]\x1b[0m        Save the loaded captions as srt, vtt or txt
+
+\x1b[91mDOWNLOADS:\x1b[0m
+    \x1b[33mdownload [id]\x1b[0m                   Download a video or playlist in the background
+    \x1b[33mdownload audio [id]\x1b[0m             Download audio only
+    \x1b[33mdownload pause [job]\x1b[0m            Stop a download, it can be continued later
+    \x1b[33mdownload resume [job]\x1b[0m           Continue a paused, cancelled or failed download
+    \x1b[33mdownload cancel [job]\x1b[0m           Stop a download for good
+    \x1b[33mdownload remove [job]\x1b[0m           Remove a job that is not running
+    \x1b[33mdownload clear\x1b[0m                  Remove all finished, failed and cancelled jobs
 
 \x1b[91mMPV:\x1b[0m
     \x1b[33mmpv prop [label]\x1b[0m                Gets mpv property
     \x1b[33mmpv sprop [label] [value]\x1b[0m       Set mpv property
     \x1b[33mmpv tprop [label] [value]\x1b[0m       Toggle a yes/no property
+    \x1b[33mmpv chapter [number]\x1b[0m            Play the video in page from a chapter
+    \x1b[33mmpv playfrom [seconds]\x1b[0m          Play the video in page from a position
     \x1b[33mmpv [command]\x1b[0m                   Runs a libmpv command
 
 \x1b[91mCUSTOM COMMANDS:\x1b[0m
@@ -1,15 +1,31 @@
-use crate::{config::MainConfig, global::functions::download_all_images, global::structs::Item};
+use crate::{
+    config::MainConfig,
+    global::functions::download_all_images,
+    global::structs::{CacheKind, Item, ResponseCache},
+};
+use home::home_dir;
 use invidious::ClientSync;
-use std::error::Error;
+use std::{error::Error, fs};
 
 pub fn load_playlist(
     client: &ClientSync,
+    cache: &ResponseCache,
     id: &str,
     mainconfig: &MainConfig,
 ) -> Result<Item, Box<dyn Error>> {
-    let playlist = Item::from_full_playlist(client.playlist(id, None)?, mainconfig.image_index);
+    let playlist = match cache.fetch(CacheKind::Playlist, id, &mainconfig.cache, || {
+        Ok(Item::from_full_playlist(
+            client.playlist(id, None)?,
+            mainconfig.image_index,
+        ))
+    }) {
+        Ok(playlist) => playlist,
+        // saved and bookmarked items can still be opened without the network
+        Err(e) => saved_info(id).ok_or(e)?,
+    };
     let videos = &playlist.fullplaylist()?.videos;
 
+    // thumbnails are removed on exit, so they are downloaded again for cached playlists
     if mainconfig.images.display() {
         download_all_images({
             let mut items = videos.iter().map(|item| item.into()).collect::<Vec<_>>();
@@ -23,13 +39,35 @@ pub fn load_playlist(
 
 pub fn load_video(
     client: &ClientSync,
+    cache: &ResponseCache,
     id: &str,
     mainconfig: &MainConfig,
 ) -> Result<Item, Box<dyn Error>> {
-    let video = Item::from_full_video(client.video(id, None)?, mainconfig.image_index);
+    let video = match cache.fetch(CacheKind::Video, id, &mainconfig.cache, || {
+        Ok(Item::from_full_video(
+            client.video(id, None)?,
+            mainconfig.image_index,
+        ))
+    }) {
+        Ok(video) => video,
+        // saved and bookmarked items can still be opened without the network
+        Err(e) => saved_info(id).ok_or(e)?,
+    };
     if mainconfig.images.display() {
         download_all_images(vec![(&video).into()]);
     }
 
     Ok(video)
 }
+
+/// info of items in watch history, library and other collections, used if the item cannot be
+/// fetched and is not cached
+fn saved_info(id: &str) -> Option<Item> {
+    fs::read_to_string(
+        home_dir()
+            .unwrap()
+            .join(format!(".cache/youtube-tui/info/{id}.json")),
+    )
+    .ok()
+    .and_then(|content| serde_json::from_str(&content).ok())
+}
@@ -0,0 +1,16 @@
+const UNITS: &[(&str, f64)] = &[
+    ("GB", 1_000_000_000_f64),
+    ("MB", 1_000_000_f64),
+    ("KB", 1_000_f64),
+];
+
+/// Turns file size into short hand like `1.56MB` instead of `1560000`
+pub fn size_text(bytes: u64) -> String {
+    for unit in UNITS.iter() {
+        if bytes >= unit.1 as u64 {
+            return format!("{:.2}{}", bytes as f64 / unit.1, unit.0);
+        }
+    }
+
+    format!("{bytes}B")
+}
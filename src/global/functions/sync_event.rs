@@ -0,0 +1,108 @@
+use crate::{
+    config::{FeedFilters, MainConfig},
+    global::{functions::notify_new_videos, structs::*},
+};
+use tui_additions::framework::Framework;
+
+/// start syncing all subscriptions in the background, returns false if a sync is already running
+///
+/// `quiet` syncs do not show progress in the message bar, only the summary when it is done
+pub fn start_sync(framework: &mut Framework, quiet: bool) -> bool {
+    let status = framework.data.global.get_mut::<SyncStatus>().unwrap();
+    if status.progress.is_some() {
+        // a scheduled sync is shown if the user asked for a sync while it is running
+        status.quiet &= quiet;
+        return false;
+    }
+
+    let instances = framework.data.global.get::<Instances>().unwrap().urls();
+    let mainconfig = framework.data.global.get::<MainConfig>().unwrap();
+    let progress = framework.data.global.get::<Subscriptions>().unwrap().sync(
+        &instances,
+        mainconfig.image_index,
+        mainconfig.images.display(),
+        mainconfig.syncing.clone(),
+        framework.data.global.get::<FeedFilters>().unwrap().clone(),
+        framework.data.global.get::<SyncStatus>().unwrap().sender(),
+    );
+
+    let status = framework.data.global.get_mut::<SyncStatus>().unwrap();
+    status.progress = (!progress.is_done()).then_some(progress);
+    status.quiet = quiet;
+
+    if !quiet {
+        // nothing to sync if all channels are synced recently
+        *framework.data.global.get_mut::<Message>().unwrap() = if progress.is_done() {
+            Message::Success(progress.summary())
+        } else {
+            Message::Message(progress.to_string())
+        };
+        framework
+            .data
+            .state
+            .get_mut::<Tasks>()
+            .unwrap()
+            .priority
+            .push(Task::RenderAll);
+    }
+
+    true
+}
+
+/// handles a channel synced in the background, updating the subscriptions and sync progress
+pub fn sync_event(synced: SyncedChannel, framework: &mut Framework) {
+    let status = framework.data.global.get::<SyncStatus>().unwrap();
+    let quiet = status.quiet;
+    let mut progress = match status.progress {
+        Some(progress) => progress,
+        None => return,
+    };
+
+    if let SyncOutcome::Success(new_videos) = &synced.outcome {
+        notify_new_videos(
+            new_videos,
+            framework.data.global.get::<MainConfig>().unwrap(),
+            framework
+                .data
+                .global
+                .get::<Instances>()
+                .unwrap()
+                .current_url(),
+        );
+        framework
+            .data
+            .global
+            .get_mut::<SyncStatus>()
+            .unwrap()
+            .new_videos += new_videos.len() as u32;
+    }
+
+    let finished = framework
+        .data
+        .global
+        .get_mut::<Subscriptions>()
+        .unwrap()
+        .synced(synced, &mut progress);
+    framework
+        .data
+        .global
+        .get_mut::<SyncStatus>()
+        .unwrap()
+        .progress = (!finished).then_some(progress);
+
+    let message = if finished {
+        Some(Message::Success(progress.summary()))
+    } else {
+        (!quiet).then(|| Message::Message(progress.to_string()))
+    };
+    if let Some(message) = message {
+        *framework.data.global.get_mut::<Message>().unwrap() = message;
+        framework
+            .data
+            .state
+            .get_mut::<Tasks>()
+            .unwrap()
+            .priority
+            .push(Task::RenderAll);
+    }
+}
@@ -66,7 +66,7 @@ pub fn update_provider(data: &mut FrameworkData) {
                 }
             },
         )],
-        Page::Feed => vec![(
+        Page::Feed(_) | Page::Timeline(_) => vec![(
             String::from("url"),
             match status.provider {
                 Provider::YouTube => String::from("https://www.youtube.com/feed/subscriptions"),
@@ -186,6 +186,8 @@ pub fn update_provider(data: &mut FrameworkData) {
                 },
             ),
         ],
+        Page::LocalPlaylist(name) => vec![(String::from("playlist-name"), name.clone())],
+        Page::Queue | Page::NowPlaying | Page::Downloads | Page::LocalPlaylists => Vec::new(),
     };
 
     data.global.get_mut::<Status>().unwrap().provider_updated = true;
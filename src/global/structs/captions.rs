@@ -0,0 +1,291 @@
+use home::home_dir;
+use serde::Deserialize;
+use std::{error::Error, fmt::Display, fs, path::PathBuf};
+use typemap::Key;
+
+use crate::global::functions::secs_display_string;
+
+/// caption tracks and the loaded transcript of a video
+#[derive(Clone, Default)]
+pub struct Captions {
+    /// id of the video the tracks belong to
+    pub id: Option<String>,
+    pub tracks: Vec<CaptionTrack>,
+    /// index of the loaded track
+    pub track: Option<usize>,
+    pub cues: Vec<Cue>,
+    /// the last searched text, used by `captions next` and `captions prev`
+    pub query: Option<String>,
+    /// index of the hovered cue in the transcript view
+    pub cursor: usize,
+    /// cue that the transcript view should move its cursor to in the next render
+    pub jump: Option<usize>,
+    /// incremented every time a track is loaded, so the transcript view knows when to update
+    pub revision: usize,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct CaptionTrack {
+    pub label: String,
+    #[serde(rename = "languageCode")]
+    pub language_code: String,
+    /// path to the WebVTT file, relative to the Invidious instance
+    pub url: String,
+}
+
+#[derive(Deserialize)]
+struct CaptionTracks {
+    captions: Vec<CaptionTrack>,
+}
+
+/// a single line in the transcript
+#[derive(Clone)]
+pub struct Cue {
+    /// start time in seconds
+    pub start: f64,
+    /// end time in seconds
+    pub end: f64,
+    pub text: String,
+}
+
+#[derive(Clone, Copy)]
+pub enum CaptionFormat {
+    Srt,
+    Vtt,
+    Txt,
+}
+
+impl Key for Captions {
+    type Value = Self;
+}
+
+impl Display for Cue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{}] {}",
+            secs_display_string(self.start as u32),
+            self.text.replace('\n', " ")
+        )
+    }
+}
+
+impl CaptionFormat {
+    pub fn from_extension(s: &str) -> Option<Self> {
+        match s {
+            "srt" => Some(Self::Srt),
+            "vtt" => Some(Self::Vtt),
+            "txt" => Some(Self::Txt),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Srt => "srt",
+            Self::Vtt => "vtt",
+            Self::Txt => "txt",
+        }
+    }
+}
+
+impl Captions {
+    /// fetch the list of caption tracks of a video, and clears the loaded transcript
+    pub fn fetch_tracks(&mut self, instance: &str, id: &str) -> Result<(), Box<dyn Error>> {
+        let res = invidious::functions::httpreq_get(&format!(
+            "{}/api/v1/captions/{id}",
+            instance.trim_end_matches('/')
+        ))?;
+        let bytes: &[u8] = res.as_ref();
+        let tracks: CaptionTracks = serde_json::from_slice(bytes)?;
+
+        *self = Self {
+            id: Some(id.to_string()),
+            tracks: tracks.captions,
+            revision: self.revision + 1,
+            ..Default::default()
+        };
+        Ok(())
+    }
+
+    /// find a track by its language code or label, case insensitive
+    pub fn find_track(&self, name: &str) -> Option<usize> {
+        let name = name.to_lowercase();
+        self.tracks.iter().position(|track| {
+            track.language_code.to_lowercase() == name || track.label.to_lowercase() == name
+        })
+    }
+
+    /// download a track and load it as the transcript
+    pub fn load_track(&mut self, instance: &str, index: usize) -> Result<(), Box<dyn Error>> {
+        let track = self.tracks.get(index).ok_or("Track does not exist")?;
+        let res = invidious::functions::httpreq_get(&format!(
+            "{}{}",
+            instance.trim_end_matches('/'),
+            track.url
+        ))?;
+        let bytes: &[u8] = res.as_ref();
+
+        self.cues = Self::parse_vtt(&String::from_utf8_lossy(bytes));
+        self.track = Some(index);
+        self.cursor = 0;
+        self.jump = None;
+        self.revision += 1;
+        Ok(())
+    }
+
+    /// parse cues from a WebVTT file, tags are removed and repeated lines in auto generated
+    /// captions are merged
+    pub fn parse_vtt(content: &str) -> Vec<Cue> {
+        let mut cues: Vec<Cue> = Vec::new();
+
+        for block in content.replace("\r\n", "\n").split("\n\n") {
+            let mut lines = block.lines().skip_while(|line| !line.contains("-->"));
+            let (start, end) = match lines.next().and_then(|line| {
+                let (start, end) = line.split_once("-->")?;
+                // cue settings such as `align:start` come after the end time
+                let end = end.split_whitespace().next()?;
+                Some((
+                    Self::parse_timestamp(start.trim())?,
+                    Self::parse_timestamp(end)?,
+                ))
+            }) {
+                Some(times) => times,
+                None => continue,
+            };
+
+            let text = lines
+                .map(|line| Self::strip_tags(line).trim().to_string())
+                .filter(|line| !line.is_empty())
+                // rolling auto generated captions repeats the previous line
+                .filter(|line| cues.last().is_none_or(|cue| !cue.text.ends_with(line)))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            if text.is_empty() {
+                continue;
+            }
+
+            cues.push(Cue { start, end, text });
+        }
+
+        cues
+    }
+
+    /// parse `hh:mm:ss.mmm` or `mm:ss.mmm` into seconds
+    fn parse_timestamp(s: &str) -> Option<f64> {
+        s.split(':').try_fold(0.0, |acc, part| {
+            Some(acc * 60.0 + part.parse::<f64>().ok()?)
+        })
+    }
+
+    fn strip_tags(line: &str) -> String {
+        let mut out = String::new();
+        let mut in_tag = false;
+        for c in line.chars() {
+            match c {
+                '<' => in_tag = true,
+                '>' if in_tag => in_tag = false,
+                _ if !in_tag => out.push(c),
+                _ => {}
+            }
+        }
+
+        out.replace("&nbsp;", " ")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&amp;", "&")
+    }
+
+    /// format a timestamp as `hh:mm:ss.mmm` (or `hh:mm:ss,mmm` with `comma`)
+    fn format_timestamp(secs: f64, comma: bool) -> String {
+        let millis = (secs * 1000.0).round() as u64;
+        format!(
+            "{:02}:{:02}:{:02}{}{:03}",
+            millis / 3600000,
+            millis / 60000 % 60,
+            millis / 1000 % 60,
+            if comma { ',' } else { '.' },
+            millis % 1000
+        )
+    }
+
+    /// the loaded transcript in the given format
+    pub fn to_format(&self, format: CaptionFormat) -> String {
+        match format {
+            CaptionFormat::Srt => self
+                .cues
+                .iter()
+                .enumerate()
+                .map(|(index, cue)| {
+                    format!(
+                        "{}\n{} --> {}\n{}\n\n",
+                        index + 1,
+                        Self::format_timestamp(cue.start, true),
+                        Self::format_timestamp(cue.end, true),
+                        cue.text
+                    )
+                })
+                .collect(),
+            CaptionFormat::Vtt => {
+                let mut out = String::from("WEBVTT\n\n");
+                self.cues.iter().for_each(|cue| {
+                    out.push_str(&format!(
+                        "{} --> {}\n{}\n\n",
+                        Self::format_timestamp(cue.start, false),
+                        Self::format_timestamp(cue.end, false),
+                        cue.text
+                    ))
+                });
+                out
+            }
+            CaptionFormat::Txt => self
+                .cues
+                .iter()
+                .map(|cue| format!("{}\n", cue.text))
+                .collect(),
+        }
+    }
+
+    /// write the loaded transcript to `~/.local/share/youtube-tui/saved/{id}.{lang}.{ext}`
+    pub fn export(&self, format: CaptionFormat) -> Result<PathBuf, Box<dyn Error>> {
+        let (id, track) = match (&self.id, self.track) {
+            (Some(id), Some(track)) => (id, &self.tracks[track]),
+            _ => return Err("No captions loaded".into()),
+        };
+
+        let path = home_dir().unwrap().join(format!(
+            ".local/share/youtube-tui/saved/{id}.{}.{}",
+            track.language_code,
+            format.extension()
+        ));
+        fs::write(&path, self.to_format(format))?;
+        Ok(path)
+    }
+
+    /// find the next (or previous) cue containing the last searched text, starting from the
+    /// cursor and wrapping around
+    pub fn search(&mut self, forward: bool) -> Option<usize> {
+        let query = self.query.as_ref()?.to_lowercase();
+        let len = self.cues.len();
+        if len == 0 {
+            return None;
+        }
+
+        let found = (1..=len)
+            .map(|offset| {
+                if forward {
+                    (self.cursor + offset) % len
+                } else {
+                    (self.cursor + len - offset % len) % len
+                }
+            })
+            .find(|index| self.cues[*index].text.to_lowercase().contains(&query))?;
+
+        self.cursor = found;
+        self.jump = Some(found);
+        Some(found)
+    }
+}
@@ -0,0 +1,426 @@
+use home::home_dir;
+use serde::{Deserialize, Serialize};
+use std::{
+    error::Error,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::Write,
+};
+use urlencoding::encode;
+
+/// comments of a video, cached to `~/.cache/youtube-tui/comments/{id}.json` until exiting
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Comments {
+    pub sort: CommentsSort,
+    /// comments sorted by top, `None` if not yet fetched
+    pub top: Option<CommentsPage>,
+    /// comments sorted by new, `None` if not yet fetched
+    pub new: Option<CommentsPage>,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommentsSort {
+    #[default]
+    Top,
+    New,
+}
+
+/// all fetched comments of one sorting
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct CommentsPage {
+    pub comments: Vec<Comment>,
+    /// token for fetching the next page, `None` if all comments are loaded
+    pub continuation: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Comment {
+    pub author: String,
+    pub author_id: String,
+    pub content: String,
+    pub published_text: String,
+    pub likes: u32,
+    pub edited: bool,
+    pub reply_count: u32,
+    pub replies: Vec<Comment>,
+    /// token for fetching the next page of replies
+    pub replies_continuation: Option<String>,
+    /// whether replies are displayed
+    #[serde(skip)]
+    pub expanded: bool,
+}
+
+/// response of `/api/v1/comments/{id}`, only with the fields that are used
+// the comment types in `invidious` keep the reply count and continuation private
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct CommentsResponse {
+    comments: Vec<CommentResponse>,
+    continuation: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct CommentResponse {
+    author: String,
+    author_id: String,
+    content: String,
+    published_text: String,
+    like_count: u32,
+    is_edited: bool,
+    replies: Option<RepliesResponse>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct RepliesResponse {
+    reply_count: u32,
+    continuation: Option<String>,
+}
+
+/// what each line in the comments view does when selected
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CommentsEntry {
+    SwitchView,
+    Sort,
+    /// index of the comment, and of the reply if it is one
+    Comment(usize, Option<usize>),
+    /// load more replies of a comment
+    MoreReplies(usize),
+    LoadMore,
+    None,
+}
+
+impl CommentsSort {
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::Top => Self::New,
+            Self::New => Self::Top,
+        }
+    }
+
+    /// value of `sort_by` in the api request
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            Self::Top => "top",
+            Self::New => "new",
+        }
+    }
+}
+
+impl Display for CommentsSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Top => "Top comments",
+            Self::New => "Newest first",
+        })
+    }
+}
+
+impl Display for Comment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({}, {} likes{}): {}",
+            self.author,
+            self.published_text,
+            self.likes,
+            if self.edited { ", edited" } else { "" },
+            self.content
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+        )
+    }
+}
+
+impl CommentsPage {
+    /// fetch a page of comments or replies from an instance, starting from `continuation`
+    pub fn fetch(
+        instance: &str,
+        id: &str,
+        sort: CommentsSort,
+        continuation: Option<&str>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut url = format!(
+            "{instance}/api/v1/comments/{id}?sort_by={}",
+            sort.as_param()
+        );
+        if let Some(continuation) = continuation {
+            url.push_str(&format!("&continuation={}", encode(continuation)));
+        }
+
+        let res = invidious::functions::httpreq_get(&url)?;
+        Self::parse(res.as_ref())
+    }
+
+    fn parse(body: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let response: CommentsResponse = serde_json::from_slice(body)?;
+        Ok(Self {
+            comments: response
+                .comments
+                .into_iter()
+                .map(|comment| {
+                    let (reply_count, replies_continuation) = match comment.replies {
+                        Some(replies) => (replies.reply_count, replies.continuation),
+                        None => (0, None),
+                    };
+                    Comment {
+                        author: comment.author,
+                        author_id: comment.author_id,
+                        content: comment.content,
+                        published_text: comment.published_text,
+                        likes: comment.like_count,
+                        edited: comment.is_edited,
+                        reply_count,
+                        replies: Vec::new(),
+                        replies_continuation,
+                        expanded: false,
+                    }
+                })
+                .collect(),
+            continuation: response.continuation,
+        })
+    }
+}
+
+impl Comments {
+    fn path(id: &str) -> std::path::PathBuf {
+        home_dir()
+            .unwrap()
+            .join(format!(".cache/youtube-tui/comments/{id}.json"))
+    }
+
+    /// load comments from cache, fetching the first page if it is not cached
+    ///
+    /// `fetch` is called with the sorting and continuation of the page to fetch, at most once
+    pub fn load<F>(id: &str, fetch: F) -> Result<Self, Box<dyn Error>>
+    where
+        F: FnOnce(CommentsSort, Option<&str>) -> Result<CommentsPage, Box<dyn Error>>,
+    {
+        let mut comments = fs::read_to_string(Self::path(id))
+            .ok()
+            .and_then(|content| serde_json::from_str::<Self>(&content).ok())
+            .unwrap_or_default();
+
+        if comments.page().is_none() {
+            comments.fetch_sort(fetch)?;
+        }
+
+        Ok(comments)
+    }
+
+    pub fn save(&self, id: &str) -> Result<(), Box<dyn Error>> {
+        let save_string = serde_json::to_string(&self)?;
+
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(Self::path(id))?;
+
+        file.write_all(save_string.as_bytes())?;
+
+        Ok(())
+    }
+
+    /// comments of the current sorting
+    pub fn page(&self) -> Option<&CommentsPage> {
+        match self.sort {
+            CommentsSort::Top => self.top.as_ref(),
+            CommentsSort::New => self.new.as_ref(),
+        }
+    }
+
+    fn page_mut(&mut self) -> &mut Option<CommentsPage> {
+        match self.sort {
+            CommentsSort::Top => &mut self.top,
+            CommentsSort::New => &mut self.new,
+        }
+    }
+
+    /// fetch the first page of the current sorting
+    pub fn fetch_sort<F>(&mut self, fetch: F) -> Result<(), Box<dyn Error>>
+    where
+        F: FnOnce(CommentsSort, Option<&str>) -> Result<CommentsPage, Box<dyn Error>>,
+    {
+        let page = fetch(self.sort, None)?;
+        *self.page_mut() = Some(page);
+        Ok(())
+    }
+
+    /// switch between top and new, fetching comments if not already cached
+    pub fn toggle_sort<F>(&mut self, fetch: F) -> Result<(), Box<dyn Error>>
+    where
+        F: FnOnce(CommentsSort, Option<&str>) -> Result<CommentsPage, Box<dyn Error>>,
+    {
+        self.sort = self.sort.toggle();
+        if self.page().is_none() {
+            if let Err(e) = self.fetch_sort(fetch) {
+                self.sort = self.sort.toggle();
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// fetch the next page of comments
+    pub fn load_more<F>(&mut self, fetch: F) -> Result<(), Box<dyn Error>>
+    where
+        F: FnOnce(CommentsSort, Option<&str>) -> Result<CommentsPage, Box<dyn Error>>,
+    {
+        let sort = self.sort;
+        let page = match self.page_mut() {
+            Some(page) => page,
+            None => return self.fetch_sort(fetch),
+        };
+        let continuation = match &page.continuation {
+            Some(continuation) => continuation,
+            None => return Ok(()),
+        };
+
+        let next = fetch(sort, Some(continuation.as_str()))?;
+        page.comments.extend(next.comments);
+        page.continuation = next.continuation;
+        Ok(())
+    }
+
+    /// expand or collapse replies of a comment, fetching the first page of replies when it is
+    /// first expanded
+    pub fn toggle_replies<F>(&mut self, fetch: F, index: usize) -> Result<(), Box<dyn Error>>
+    where
+        F: FnOnce(CommentsSort, Option<&str>) -> Result<CommentsPage, Box<dyn Error>>,
+    {
+        let comment = match self.page_mut() {
+            Some(page) => &mut page.comments[index],
+            None => return Ok(()),
+        };
+
+        if comment.reply_count == 0 {
+            return Ok(());
+        }
+
+        if !comment.expanded && comment.replies.is_empty() {
+            self.load_replies(fetch, index)?;
+        }
+
+        let comment = &mut self.page_mut().as_mut().unwrap().comments[index];
+        comment.expanded = !comment.expanded;
+        Ok(())
+    }
+
+    /// fetch the next page of replies of a comment
+    pub fn load_replies<F>(&mut self, fetch: F, index: usize) -> Result<(), Box<dyn Error>>
+    where
+        F: FnOnce(CommentsSort, Option<&str>) -> Result<CommentsPage, Box<dyn Error>>,
+    {
+        let sort = self.sort;
+        let comment = match self.page_mut() {
+            Some(page) => &mut page.comments[index],
+            None => return Ok(()),
+        };
+        let continuation = match &comment.replies_continuation {
+            Some(continuation) => continuation,
+            None => return Ok(()),
+        };
+
+        let next = fetch(sort, Some(continuation.as_str()))?;
+        comment.replies.extend(next.comments);
+        comment.replies_continuation = next.continuation;
+        Ok(())
+    }
+
+    /// lines to display in the comments view, and what they do when selected
+    pub fn entries(&self) -> Vec<(String, CommentsEntry)> {
+        let mut entries = vec![
+            (String::from("Switch view"), CommentsEntry::SwitchView),
+            (format!("Sort: {}", self.sort), CommentsEntry::Sort),
+        ];
+
+        let page = match self.page() {
+            Some(page) => page,
+            None => return entries,
+        };
+
+        if page.comments.is_empty() {
+            entries.push((String::from("No comments found"), CommentsEntry::None));
+        }
+
+        for (index, comment) in page.comments.iter().enumerate() {
+            entries.push((comment.to_string(), CommentsEntry::Comment(index, None)));
+
+            if comment.reply_count == 0 {
+                continue;
+            }
+
+            if !comment.expanded {
+                entries.push((
+                    format!("  [+] {} replies", comment.reply_count),
+                    CommentsEntry::Comment(index, None),
+                ));
+                continue;
+            }
+
+            entries.extend(
+                comment
+                    .replies
+                    .iter()
+                    .enumerate()
+                    .map(|(reply_index, reply)| {
+                        (
+                            format!("  ↳ {reply}"),
+                            CommentsEntry::Comment(index, Some(reply_index)),
+                        )
+                    }),
+            );
+
+            if comment.replies_continuation.is_some() {
+                entries.push((
+                    String::from("  [+] More replies"),
+                    CommentsEntry::MoreReplies(index),
+                ));
+            }
+        }
+
+        if page.continuation.is_some() {
+            entries.push((String::from("Load more comments"), CommentsEntry::LoadMore));
+        }
+
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reads_replies_and_continuation() {
+        let page =
+            CommentsPage::parse(include_str!("../../../tests/fixtures/comments.json").as_bytes())
+                .unwrap();
+
+        assert_eq!(page.continuation.as_deref(), Some("next-page-token"));
+        assert_eq!(page.comments.len(), 2);
+
+        let first = &page.comments[0];
+        assert_eq!(first.author_id, "UC0123456789abcdefghijkl");
+        assert_eq!(first.likes, 42);
+        assert!(first.edited);
+        assert_eq!(first.reply_count, 3);
+        assert_eq!(first.replies_continuation.as_deref(), Some("replies-token"));
+        assert_eq!(
+            first.to_string(),
+            "First Author (1 year ago, 42 likes, edited): Great video, thanks!"
+        );
+
+        let second = &page.comments[1];
+        assert_eq!(second.reply_count, 0);
+        assert!(second.replies_continuation.is_none());
+    }
+
+    #[test]
+    fn parse_rejects_invalid_json() {
+        assert!(CommentsPage::parse(b"not json").is_err());
+    }
+}
@@ -0,0 +1,485 @@
+use crate::{
+    config::MainConfig,
+    global::{functions::retry_delay, structs::Item},
+};
+use home::home_dir;
+use serde::{Deserialize, Serialize};
+use std::{
+    error::Error,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    process::{Child, Command, Stdio},
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::Instant,
+};
+use typemap::Key;
+
+/// download jobs, saved to `~/.local/share/youtube-tui/downloads.json`
+#[derive(Clone)]
+pub struct Downloads {
+    pub jobs: Vec<DownloadJob>,
+    sender: mpsc::Sender<DownloadEvent>,
+    /// events from downloader processes, should be handled by the main thread with
+    /// `download_event()`
+    pub events: Arc<Mutex<mpsc::Receiver<DownloadEvent>>>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DownloadJob {
+    /// used to refer to the job in commands, unique within the download list
+    pub job: u32,
+    /// id of the video or playlist
+    pub id: String,
+    pub title: String,
+    /// urls to download, a playlist contains multiple videos
+    pub urls: Vec<String>,
+    /// only download audio
+    pub audio: bool,
+    /// a playlist is downloaded into its own folder
+    pub is_playlist: bool,
+    /// added to library when the download is done
+    pub item: Item,
+    pub state: DownloadState,
+    /// number of failed attempts since the job is queued
+    pub attempts: u32,
+    #[serde(skip)]
+    pub progress: Option<DownloadProgress>,
+    /// the last error printed by the downloader
+    #[serde(skip)]
+    pub error: Option<String>,
+    /// the downloader process, stays `Some` until the process exits even if it is killed
+    #[serde(skip)]
+    child: Option<Arc<Mutex<Child>>>,
+    /// a failed job waits until this time before retrying
+    #[serde(skip)]
+    retry_at: Option<Instant>,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum DownloadState {
+    Queued,
+    Running,
+    Paused,
+    Cancelled,
+    Done,
+    Failed(String),
+}
+
+/// progress of the file currently being downloaded
+#[derive(Clone, Default)]
+pub struct DownloadProgress {
+    pub percentage: f32,
+    pub size: String,
+    pub speed: String,
+    pub eta: String,
+}
+
+pub enum DownloadEvent {
+    Progress(u32, DownloadProgress),
+    /// an `ERROR:` line from the downloader
+    Error(u32, String),
+    /// the downloader exited, with whether it is successful
+    Finished(u32, bool),
+}
+
+impl Key for Downloads {
+    type Value = Self;
+}
+
+impl Default for Downloads {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            jobs: Vec::new(),
+            sender,
+            events: Arc::new(Mutex::new(receiver)),
+        }
+    }
+}
+
+impl Display for DownloadState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Queued => f.write_str("Queued"),
+            Self::Running => f.write_str("Downloading"),
+            Self::Paused => f.write_str("Paused"),
+            Self::Cancelled => f.write_str("Cancelled"),
+            Self::Done => f.write_str("Done"),
+            Self::Failed(e) => write!(f, "Failed: {e}"),
+        }
+    }
+}
+
+impl Display for DownloadJob {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "#{} [{}] {}",
+            self.job,
+            if self.audio { "audio" } else { "video" },
+            self.title
+        )?;
+
+        match (&self.state, &self.progress, self.retry_at) {
+            (DownloadState::Running, Some(progress), _) => write!(
+                f,
+                " - {:.1}% of {} at {} ETA {}",
+                progress.percentage, progress.size, progress.speed, progress.eta
+            ),
+            (DownloadState::Queued, _, Some(retry_at)) => write!(
+                f,
+                " - Retrying in {}s (attempt {})",
+                retry_at.saturating_duration_since(Instant::now()).as_secs(),
+                self.attempts + 1
+            ),
+            (state, _, _) => write!(f, " - {state}"),
+        }
+    }
+}
+
+impl DownloadProgress {
+    /// parse a progress line from yt-dlp (with `--newline`), such as
+    /// `[download]  42.3% of ~10.00MiB at  1.00MiB/s ETA 00:10`
+    pub fn from_line(line: &str) -> Option<Self> {
+        let words = line
+            .strip_prefix("[download]")?
+            .split_whitespace()
+            .collect::<Vec<_>>();
+        let percentage = words.first()?.strip_suffix('%')?.parse().ok()?;
+        let after = |label: &str| {
+            words
+                .iter()
+                .position(|word| *word == label)
+                .and_then(|index| words.get(index + 1))
+                .map(|word| word.trim_start_matches('~').to_string())
+                .unwrap_or_default()
+        };
+
+        Some(Self {
+            percentage,
+            size: after("of"),
+            speed: after("at"),
+            eta: after("ETA"),
+        })
+    }
+}
+
+impl Downloads {
+    const PATH: &'static str = ".local/share/youtube-tui/downloads.json";
+
+    /// loads jobs from file, downloads interrupted by exiting are queued again
+    pub fn load() -> Self {
+        let mut downloads = Self {
+            jobs: fs::read_to_string(home_dir().unwrap().join(Self::PATH))
+                .ok()
+                .and_then(|content| serde_json::from_str(&content).ok())
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+
+        downloads
+            .jobs
+            .iter_mut()
+            .filter(|job| job.state == DownloadState::Running)
+            .for_each(|job| job.state = DownloadState::Queued);
+
+        downloads
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        let save_string = serde_json::to_string(&self.jobs)?;
+
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(home_dir().unwrap().join(Self::PATH))?;
+
+        file.write_all(save_string.as_bytes())?;
+
+        Ok(())
+    }
+
+    pub fn get_mut(&mut self, job: u32) -> Option<&mut DownloadJob> {
+        self.jobs.iter_mut().find(|item| item.job == job)
+    }
+
+    /// add a job to the list, returns its job number
+    pub fn push(&mut self, item: Item, urls: Vec<String>, audio: bool) -> u32 {
+        let job = self.jobs.iter().map(|job| job.job + 1).max().unwrap_or(1);
+        self.jobs.push(DownloadJob {
+            job,
+            id: item.id().unwrap_or_default().to_string(),
+            title: item.to_string(),
+            urls,
+            audio,
+            is_playlist: matches!(item, Item::FullPlaylist(_)),
+            item,
+            state: DownloadState::Queued,
+            attempts: 0,
+            progress: None,
+            error: None,
+            child: None,
+            retry_at: None,
+        });
+        job
+    }
+
+    /// number of downloader processes that have not exited
+    pub fn running(&self) -> usize {
+        self.jobs.iter().filter(|job| job.child.is_some()).count()
+    }
+
+    /// start queued jobs until `max_concurrent` downloads are running, using
+    /// `youtube-downloader` and `save-path` in `env` of the main config, returns whether any job
+    /// is started
+    pub fn start_queued(&mut self, mainconfig: &MainConfig) -> bool {
+        let slots = mainconfig
+            .downloads
+            .max_concurrent
+            .saturating_sub(self.running());
+        if slots == 0 {
+            return false;
+        }
+
+        let downloader = mainconfig
+            .env
+            .get("youtube-downloader")
+            .map(String::as_str)
+            .unwrap_or("yt-dlp");
+        let save_path = mainconfig
+            .env
+            .get("save-path")
+            .cloned()
+            .unwrap_or_default()
+            .replacen(
+                "~/",
+                &format!("{}/", home_dir().unwrap().to_string_lossy()),
+                1,
+            );
+
+        let now = Instant::now();
+        let mut started = false;
+        for job in self
+            .jobs
+            .iter_mut()
+            .filter(|job| {
+                job.state == DownloadState::Queued
+                    && job.child.is_none()
+                    && job.retry_at.is_none_or(|retry_at| retry_at <= now)
+            })
+            .take(slots)
+        {
+            job.state = DownloadState::Running;
+            job.progress = None;
+            job.error = None;
+            job.retry_at = None;
+            started = true;
+            if let Err(e) = job.spawn(
+                self.sender.clone(),
+                &mainconfig.shell,
+                downloader,
+                &save_path,
+            ) {
+                job.state = DownloadState::Failed(e.to_string());
+            }
+        }
+
+        started
+    }
+
+    /// ids of jobs that are not finished, their partly downloaded files should be kept
+    pub fn active_ids(&self) -> Vec<&str> {
+        self.jobs
+            .iter()
+            .filter(|job| {
+                matches!(
+                    job.state,
+                    DownloadState::Queued | DownloadState::Running | DownloadState::Paused
+                )
+            })
+            .map(|job| job.id.as_str())
+            .collect()
+    }
+
+    /// whether there are queued jobs waiting to retry
+    pub fn has_retries(&self) -> bool {
+        self.jobs
+            .iter()
+            .any(|job| job.state == DownloadState::Queued && job.retry_at.is_some())
+    }
+
+    /// called when the downloader of a job exits, a failed job is queued again with a delay that
+    /// doubles each time (up to 5 minutes) until it runs out of retries, returns the new state of the job
+    pub fn finished(
+        &mut self,
+        job: u32,
+        result: Result<(), String>,
+        mainconfig: &MainConfig,
+    ) -> Option<DownloadState> {
+        let job = self.get_mut(job)?;
+        job.child = None;
+        job.progress = None;
+
+        // paused or cancelled jobs are killed, that is not a failure
+        if job.state != DownloadState::Running {
+            return Some(job.state.clone());
+        }
+
+        job.state = match result {
+            Ok(()) => DownloadState::Done,
+            Err(_) if job.attempts < mainconfig.downloads.retries => {
+                job.retry_at = Some(
+                    Instant::now()
+                        + retry_delay(mainconfig.downloads.retry_delay_secs, job.attempts),
+                );
+                job.attempts += 1;
+                DownloadState::Queued
+            }
+            Err(e) => DownloadState::Failed(e),
+        };
+
+        Some(job.state.clone())
+    }
+
+    /// stop a queued or running job, it can be continued with `resume()`
+    pub fn pause(&mut self, job: u32) -> Result<(), Box<dyn Error>> {
+        let job = self.get_mut(job).ok_or("Job does not exist")?;
+        if !matches!(job.state, DownloadState::Queued | DownloadState::Running) {
+            return Err(format!("Cannot pause a job that is {}", job.state).into());
+        }
+
+        job.state = DownloadState::Paused;
+        job.retry_at = None;
+        job.kill();
+        Ok(())
+    }
+
+    /// queue a paused, cancelled or failed job again, with retries reset
+    pub fn resume(&mut self, job: u32) -> Result<(), Box<dyn Error>> {
+        let job = self.get_mut(job).ok_or("Job does not exist")?;
+        if !matches!(
+            job.state,
+            DownloadState::Paused | DownloadState::Cancelled | DownloadState::Failed(_)
+        ) {
+            return Err(format!("Cannot resume a job that is {}", job.state).into());
+        }
+
+        job.state = DownloadState::Queued;
+        job.attempts = 0;
+        job.retry_at = None;
+        Ok(())
+    }
+
+    /// stop a job for good, partly downloaded files are left as is
+    pub fn cancel(&mut self, job: u32) -> Result<(), Box<dyn Error>> {
+        let job = self.get_mut(job).ok_or("Job does not exist")?;
+        if !matches!(
+            job.state,
+            DownloadState::Queued | DownloadState::Running | DownloadState::Paused
+        ) {
+            return Err(format!("Cannot cancel a job that is {}", job.state).into());
+        }
+
+        job.state = DownloadState::Cancelled;
+        job.retry_at = None;
+        job.kill();
+        Ok(())
+    }
+
+    /// kill all downloader processes, running jobs stay running so they are continued on next
+    /// launch
+    pub fn stop_all(&mut self) {
+        self.jobs.iter_mut().for_each(DownloadJob::kill);
+    }
+
+    /// remove finished, failed and cancelled jobs
+    pub fn clear(&mut self) {
+        self.jobs.retain(|job| {
+            matches!(
+                job.state,
+                DownloadState::Queued | DownloadState::Running | DownloadState::Paused
+            )
+        });
+    }
+}
+
+impl DownloadJob {
+    /// the shell command that runs the downloader
+    pub fn command(&self, downloader: &str, save_path: &str) -> String {
+        let output = if self.is_playlist {
+            format!(
+                "{save_path}{}[{}]/%(title)s[%(id)s].%(ext)s",
+                self.title, self.id
+            )
+        } else {
+            format!("{save_path}%(title)s[%(id)s].%(ext)s")
+        };
+
+        // `exec` replaces the shell so killing the process stops the downloader
+        format!(
+            "exec {downloader} {} --newline{} -o '{}' 2>&1",
+            self.urls
+                .iter()
+                .map(|url| format!("'{url}'"))
+                .collect::<Vec<_>>()
+                .join(" "),
+            if self.audio { " -x" } else { "" },
+            output.replace('\'', "'\\''")
+        )
+    }
+
+    fn kill(&mut self) {
+        if let Some(child) = &self.child {
+            let _ = child.lock().unwrap().kill();
+        }
+    }
+
+    /// run the downloader in a new thread, sending progress updates through `sender`
+    fn spawn(
+        &mut self,
+        sender: mpsc::Sender<DownloadEvent>,
+        shell: &str,
+        downloader: &str,
+        save_path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut child = Command::new(shell)
+            .args(["-c", &self.command(downloader, save_path)])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let stdout = child.stdout.take().ok_or("Cannot read downloader output")?;
+        let child = Arc::new(Mutex::new(child));
+        self.child = Some(child.clone());
+        let job = self.job;
+
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                let event = if let Some(progress) = DownloadProgress::from_line(&line) {
+                    DownloadEvent::Progress(job, progress)
+                } else if let Some(e) = line.strip_prefix("ERROR:") {
+                    DownloadEvent::Error(job, e.trim().to_string())
+                } else {
+                    continue;
+                };
+
+                if sender.send(event).is_err() {
+                    return;
+                }
+            }
+
+            let success = child
+                .lock()
+                .unwrap()
+                .wait()
+                .map(|status| status.success())
+                .unwrap_or(false);
+            let _ = sender.send(DownloadEvent::Finished(job, success));
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,394 @@
+use super::{Item, Library, LocalPlaylists, MiniVideoItem, WatchHistory};
+use crate::global::{
+    functions::{date_text, expand_path, from_video_url, secs_display_string, viewcount_text},
+    traits::{Collection, CollectionNoId},
+};
+use serde_json::Value;
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt::Display,
+    fs,
+};
+
+/// backup formats that watch history and playlists can be imported from
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum HistoryFormat {
+    /// tables from `newpipe.db` exported as json
+    NewPipeJson,
+    /// FreeTube `history.db`
+    FreeTubeHistory,
+    /// FreeTube `playlists.db`
+    FreeTubePlaylists,
+}
+
+/// videos read from a backup file
+pub struct ImportedHistory {
+    pub format: HistoryFormat,
+    /// watched videos, oldest first
+    pub watched: Vec<Item>,
+    /// (playlist name, videos)
+    pub playlists: Vec<(String, Vec<Item>)>,
+}
+
+/// result of importing a backup file
+pub struct HistoryImportSummary {
+    pub format: HistoryFormat,
+    pub watched: usize,
+    pub playlists: usize,
+    pub playlist_videos: usize,
+}
+
+impl Display for HistoryFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::NewPipeJson => "NewPipe",
+            Self::FreeTubeHistory => "FreeTube history",
+            Self::FreeTubePlaylists => "FreeTube playlists",
+        })
+    }
+}
+
+impl Display for HistoryImportSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Imported {} watched video{} and {} playlist{} ({} videos) from {}",
+            self.watched,
+            if self.watched == 1 { "" } else { "s" },
+            self.playlists,
+            if self.playlists == 1 { "" } else { "s" },
+            self.playlist_videos,
+            self.format
+        )
+    }
+}
+
+impl HistoryFormat {
+    /// guess the format from the content of a file
+    pub fn detect(content: &str) -> Option<Self> {
+        let content = content.trim_start_matches('\u{feff}').trim();
+
+        // a newpipe export is a single json object, freetube databases are one object per line
+        if let Ok(Value::Object(object)) = serde_json::from_str::<Value>(content) {
+            if object.contains_key("streams") {
+                return Some(Self::NewPipeJson);
+            }
+        }
+
+        nedb_documents(content).iter().find_map(|document| {
+            if document.get("playlistName").is_some() {
+                Some(Self::FreeTubePlaylists)
+            } else if document.get("videoId").is_some() {
+                Some(Self::FreeTubeHistory)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// read videos from a file of this format
+    pub fn parse(&self, content: &str) -> Result<ImportedHistory, Box<dyn Error>> {
+        let content = content.trim_start_matches('\u{feff}');
+        let mut imported = ImportedHistory {
+            format: *self,
+            watched: Vec::new(),
+            playlists: Vec::new(),
+        };
+
+        match self {
+            // expects the `streams`, `stream_history`, `playlists` and `playlist_stream_join`
+            // tables of `newpipe.db`, each as an array of rows
+            Self::NewPipeJson => {
+                let tables: Value = serde_json::from_str(content)?;
+                let table = |name: &str| {
+                    tables
+                        .get(name)
+                        .and_then(Value::as_array)
+                        .cloned()
+                        .unwrap_or_default()
+                };
+
+                let streams = table("streams")
+                    .iter()
+                    .filter(|stream| stream.get("service_id").and_then(as_u64).unwrap_or(0) == 0)
+                    .filter_map(|stream| Some((stream.get("uid").and_then(as_u64)?, stream)))
+                    .filter_map(|(uid, stream)| Some((uid, newpipe_video(stream)?)))
+                    .collect::<HashMap<_, _>>();
+
+                let mut history = table("stream_history");
+                history.sort_by_key(|entry| entry.get("access_date").and_then(as_u64));
+                imported.watched = history
+                    .iter()
+                    .filter_map(|entry| streams.get(&entry.get("stream_id").and_then(as_u64)?))
+                    .cloned()
+                    .collect();
+
+                let mut joins = table("playlist_stream_join");
+                joins.sort_by_key(|join| join.get("join_index").and_then(as_u64));
+                imported.playlists = table("playlists")
+                    .iter()
+                    .filter_map(|playlist| {
+                        let uid = playlist.get("uid").and_then(as_u64)?;
+                        let name = playlist.get("name")?.as_str()?.to_string();
+                        let videos = joins
+                            .iter()
+                            .filter(|join| join.get("playlist_id").and_then(as_u64) == Some(uid))
+                            .filter_map(|join| {
+                                streams.get(&join.get("stream_id").and_then(as_u64)?)
+                            })
+                            .cloned()
+                            .collect();
+                        Some((name, videos))
+                    })
+                    .collect();
+            }
+            Self::FreeTubeHistory => {
+                let mut history = nedb_documents(content);
+                history.sort_by_key(|entry| entry.get("timeWatched").and_then(as_u64));
+                imported.watched = history.iter().filter_map(freetube_video).collect();
+            }
+            Self::FreeTubePlaylists => {
+                imported.playlists = nedb_documents(content)
+                    .iter()
+                    .filter_map(|playlist| {
+                        let name = playlist.get("playlistName")?.as_str()?.to_string();
+                        let videos = playlist
+                            .get("videos")
+                            .and_then(Value::as_array)
+                            .map(|videos| videos.iter().filter_map(freetube_video).collect())
+                            .unwrap_or_default();
+                        Some((name, videos))
+                    })
+                    .collect();
+            }
+        }
+
+        Ok(imported)
+    }
+}
+
+impl ImportedHistory {
+    /// read a backup file, the format is detected from the content
+    pub fn load(path: &str) -> Result<Self, Box<dyn Error>> {
+        let content = fs::read_to_string(expand_path(path))?;
+        HistoryFormat::detect(&content)
+            .ok_or("Unknown file format")?
+            .parse(&content)
+    }
+
+    /// watched videos are added to watch history, videos in playlists are added to library and a
+    /// new local playlist named after the imported one
+    pub fn apply(
+        self,
+        history: &mut WatchHistory,
+        library: &mut Library,
+        localplaylists: &mut LocalPlaylists,
+    ) -> Result<HistoryImportSummary, Box<dyn Error>> {
+        let mut summary = HistoryImportSummary {
+            format: self.format,
+            // the same video can be watched many times, but is only once in watch history
+            watched: self
+                .watched
+                .iter()
+                .filter_map(Item::id)
+                .collect::<HashSet<_>>()
+                .len(),
+            playlists: self.playlists.len(),
+            playlist_videos: 0,
+        };
+
+        for item in self.watched {
+            history.push(item)?;
+        }
+        history.save()?;
+
+        for (name, videos) in self.playlists {
+            for item in videos.iter() {
+                library.push(item.clone())?;
+            }
+
+            let name = playlist_name(&name, localplaylists);
+            localplaylists.create(&name)?;
+            summary.playlist_videos += localplaylists.get_mut(&name).unwrap().add(videos);
+        }
+        library.save()?;
+        localplaylists.save()?;
+
+        Ok(summary)
+    }
+}
+
+/// local playlist names cannot contain spaces or path separators, so they are replaced with `-`,
+/// a number is appended if a playlist of the same name already exists
+fn playlist_name(name: &str, localplaylists: &LocalPlaylists) -> String {
+    let name = name
+        .split(|c: char| c == '/' || c == '\\' || c.is_whitespace())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    let name = match name.trim_start_matches('.') {
+        "" => "imported",
+        name => name,
+    };
+
+    let mut unique = name.to_string();
+    let mut n = 2;
+    while localplaylists.get(&unique).is_some() {
+        unique = format!("{name}-{n}");
+        n += 1;
+    }
+    unique
+}
+
+/// documents in a NeDB database file (one json object per line), where later lines replace
+/// earlier lines with the same `_id` and deleted documents are removed
+fn nedb_documents(content: &str) -> Vec<Value> {
+    let mut documents: Vec<Option<Value>> = Vec::new();
+    let mut positions = HashMap::new();
+
+    content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(Value::is_object)
+        .for_each(|document| {
+            let id = document
+                .get("_id")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            let deleted = document.get("$$deleted").and_then(Value::as_bool) == Some(true);
+
+            match id.as_ref().and_then(|id| positions.get(id).copied()) {
+                Some(position) => documents[position] = (!deleted).then_some(document),
+                None => {
+                    if let Some(id) = id {
+                        positions.insert(id, documents.len());
+                    }
+                    documents.push((!deleted).then_some(document));
+                }
+            }
+        });
+
+    documents.into_iter().flatten().collect()
+}
+
+/// numbers in backups are sometimes stored as strings
+fn as_u64(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(number) => number
+            .as_u64()
+            .or_else(|| number.as_f64().map(|number| number as u64)),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// both NewPipe and FreeTube store timestamps in milliseconds
+fn timestamp(millis: Option<u64>) -> Option<u64> {
+    millis
+        .filter(|millis| *millis != 0)
+        .map(|millis| millis / 1000)
+}
+
+fn newpipe_video(stream: &Value) -> Option<Item> {
+    let id = from_video_url(stream.get("url")?.as_str()?).ok()?;
+    let timestamp = timestamp(stream.get("upload_date").and_then(as_u64));
+
+    Some(Item::MiniVideo(MiniVideoItem {
+        title: stream.get("title")?.as_str()?.to_string(),
+        thumbnail_url: stream
+            .get("thumbnail_url")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| thumbnail_url(&id)),
+        length: secs_display_string(
+            stream.get("duration").and_then(as_u64).unwrap_or_default() as u32
+        ),
+        views: stream
+            .get("view_count")
+            .and_then(as_u64)
+            .map(viewcount_text),
+        channel: stream
+            .get("uploader")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        channel_id: stream
+            .get("uploader_url")
+            .and_then(Value::as_str)
+            .and_then(|url| Some(url.split_once("/channel/")?.1.to_string()))
+            .unwrap_or_default(),
+        published: timestamp.map(date_text),
+        timestamp,
+        description: None,
+        id,
+    }))
+}
+
+fn freetube_video(video: &Value) -> Option<Item> {
+    let id = video.get("videoId")?.as_str()?.to_string();
+    let timestamp = timestamp(video.get("published").and_then(as_u64));
+
+    Some(Item::MiniVideo(MiniVideoItem {
+        title: video.get("title")?.as_str()?.to_string(),
+        thumbnail_url: thumbnail_url(&id),
+        length: secs_display_string(
+            video
+                .get("lengthSeconds")
+                .and_then(as_u64)
+                .unwrap_or_default() as u32,
+        ),
+        views: video.get("viewCount").and_then(as_u64).map(viewcount_text),
+        channel: video
+            .get("author")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        channel_id: video
+            .get("authorId")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        published: timestamp.map(date_text),
+        timestamp,
+        description: video
+            .get("description")
+            .and_then(Value::as_str)
+            .filter(|description| !description.is_empty())
+            .map(str::to_string),
+        id,
+    }))
+}
+
+/// backups may not store thumbnails, so one is taken from youtube directly
+fn thumbnail_url(id: &str) -> String {
+    format!("https://i.ytimg.com/vi/{id}/mqdefault.jpg")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn playlist_names_are_sanitized_and_unique() {
+        let mut localplaylists = LocalPlaylists::default();
+        localplaylists.create("Watch-later").unwrap();
+
+        assert_eq!(
+            playlist_name("My  music/mix", &localplaylists),
+            "My-music-mix"
+        );
+        assert_eq!(playlist_name("../..", &localplaylists), "-..");
+        assert_eq!(playlist_name("  ", &localplaylists), "imported");
+        assert_eq!(
+            playlist_name("Watch later", &localplaylists),
+            "Watch-later-2"
+        );
+
+        localplaylists.create("Watch-later-2").unwrap();
+        assert_eq!(
+            playlist_name("Watch later", &localplaylists),
+            "Watch-later-3"
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use super::Discovered;
+use crate::config::{MainConfig, WriteConfig};
+use serde_json::Value;
+use std::{
+    error::Error,
+    fmt::Display,
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+use typemap::Key;
+
+/// invidious instances from main config, with their health checked in the background
+#[derive(Clone)]
+pub struct Instances {
+    sender: mpsc::Sender<InstanceEvent>,
+    /// results of health checks and discovery, should be handled by the main thread with
+    /// `instance_event()`
+    pub events: Arc<Mutex<mpsc::Receiver<InstanceEvent>>>,
+    /// all instances, `invidious_instance` first
+    pub list: Vec<(String, InstanceHealth)>,
+    /// index of the instance in use
+    pub current: usize,
+    /// number of checks started by `instance test` that are not finished
+    pub testing: usize,
+    /// when all instances should be checked again, `None` if they are not checked again
+    pub next_check: Option<Instant>,
+    /// number of pages in a row that failed to load from all instances
+    pub failures: u32,
+}
+
+/// result of the last health check of an instance
+#[derive(Clone, PartialEq, Eq)]
+pub enum InstanceHealth {
+    Unknown,
+    /// with the response time
+    Healthy(Duration),
+    /// with the error message
+    Unhealthy(String),
+}
+
+/// result of work done in the background for `Instances`
+pub enum InstanceEvent {
+    /// an instance with its health
+    Checked(String, InstanceHealth),
+    /// the main config saved with the discovered instances, `None` if no healthy instances are
+    /// found
+    Discovered(Result<Option<(Box<MainConfig>, Discovered)>, String>),
+}
+
+impl Key for Instances {
+    type Value = Self;
+}
+
+impl Display for InstanceHealth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unknown => f.write_str("unknown"),
+            Self::Healthy(latency) => write!(f, "{}ms", latency.as_millis()),
+            Self::Unhealthy(e) => write!(f, "down: {e}"),
+        }
+    }
+}
+
+impl Instances {
+    pub fn new(mainconfig: &MainConfig) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let mut list: Vec<(String, InstanceHealth)> = Vec::new();
+        for url in [&mainconfig.invidious_instance]
+            .into_iter()
+            .chain(mainconfig.instances.fallbacks.iter())
+        {
+            let url = url.trim_end_matches('/').to_string();
+            if !list.iter().any(|(existing, _)| *existing == url) {
+                list.push((url, InstanceHealth::Unknown));
+            }
+        }
+
+        Self {
+            sender,
+            events: Arc::new(Mutex::new(receiver)),
+            list,
+            current: 0,
+            testing: 0,
+            // checked right after launching
+            next_check: Some(Instant::now()),
+            failures: 0,
+        }
+    }
+
+    /// url of the instance in use
+    pub fn current_url(&self) -> &str {
+        &self.list[self.current].0
+    }
+
+    /// check the health of an instance and record the result, blocks until it responds or times
+    /// out
+    pub fn check_now(&mut self, index: usize, timeout_secs: u64) -> InstanceHealth {
+        let health = check(&self.list[index].0, Duration::from_secs(timeout_secs));
+        self.list[index].1 = health.clone();
+        health
+    }
+
+    /// check all instances in the background, results are sent to `events`
+    pub fn check_all(&self, timeout_secs: u64) {
+        for (url, _) in self.list.iter() {
+            let url = url.clone();
+            let sender = self.sender.clone();
+            thread::spawn(move || {
+                let health = check(&url, Duration::from_secs(timeout_secs));
+                let _ = sender.send(InstanceEvent::Checked(url, health));
+            });
+        }
+    }
+
+    /// discover public instances in the background and save them to main config, the result is
+    /// sent to `events`
+    pub fn discover(&self, region: Option<String>) {
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let res = (|| -> Result<Option<(Box<MainConfig>, Discovered)>, Box<dyn Error>> {
+                // loaded again so options that are changed in the TUI are not saved
+                let mut mainconfig = MainConfig::load(WriteConfig::Dont)?;
+                let discovered = Discovered::fetch(region.as_deref(), &mainconfig)?;
+                if !discovered.apply(&mut mainconfig) {
+                    return Ok(None);
+                }
+                mainconfig.save()?;
+                Ok(Some((mainconfig, discovered)))
+            })();
+            let _ = sender.send(InstanceEvent::Discovered(res.map_err(|e| e.to_string())));
+        });
+    }
+
+    /// update the health of an instance, returns false if the instance is not in the list
+    pub fn set_health(&mut self, url: &str, health: InstanceHealth) -> bool {
+        match self.list.iter_mut().find(|(existing, _)| existing == url) {
+            Some((_, existing)) => {
+                *existing = health;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// indices of instances after the current one that are not known to be down, in the order
+    /// they should be failed over to
+    pub fn fallbacks(&self) -> Vec<usize> {
+        (1..self.list.len())
+            .map(|offset| (self.current + offset) % self.list.len())
+            .filter(|index| !matches!(self.list[*index].1, InstanceHealth::Unhealthy(_)))
+            .collect()
+    }
+
+    /// urls of the current instance, then its fallbacks
+    pub fn urls(&self) -> Vec<String> {
+        [self.current]
+            .into_iter()
+            .chain(self.fallbacks())
+            .map(|index| self.list[index].0.clone())
+            .collect()
+    }
+
+    /// e.g. `* 1. https://a.com (120ms), 2. https://b.com (down: timed out)`, the current instance
+    /// is marked with `*`
+    pub fn list_text(&self) -> String {
+        self.list
+            .iter()
+            .enumerate()
+            .map(|(index, (url, health))| {
+                format!(
+                    "{}{}. {url} ({health})",
+                    if index == self.current { "* " } else { "" },
+                    index + 1
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// e.g. `2/3 instances healthy`
+    pub fn summary(&self) -> String {
+        format!(
+            "{}/{} instances healthy",
+            self.list
+                .iter()
+                .filter(|(_, health)| matches!(health, InstanceHealth::Healthy(_)))
+                .count(),
+            self.list.len()
+        )
+    }
+}
+
+/// request the stats api of an instance, it is healthy if a json object without errors is
+/// returned before the timeout
+pub(super) fn check(url: &str, timeout: Duration) -> InstanceHealth {
+    let (tx, rx) = mpsc::channel();
+    let start = Instant::now();
+    let stats_url = format!("{url}/api/v1/stats");
+    // a request that timed out keeps running in the background, but its result is ignored
+    thread::spawn(move || {
+        let _ = tx.send(
+            invidious::functions::httpreq_get(&stats_url)
+                .map(|res| {
+                    let body: &[u8] = res.as_ref();
+                    serde_json::from_slice::<Value>(body).ok()
+                })
+                .map_err(|e| e.to_string()),
+        );
+    });
+
+    match rx.recv_timeout(timeout) {
+        Ok(Ok(Some(Value::Object(object)))) => match object.get("error") {
+            Some(e) => InstanceHealth::Unhealthy(
+                e.as_str()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| e.to_string()),
+            ),
+            None => InstanceHealth::Healthy(start.elapsed()),
+        },
+        Ok(Ok(_)) => InstanceHealth::Unhealthy(String::from("invalid response")),
+        Ok(Err(e)) => InstanceHealth::Unhealthy(e),
+        Err(_) => InstanceHealth::Unhealthy(String::from("timed out")),
+    }
+}
@@ -114,6 +114,99 @@ pub struct FullVideoItem {
     pub likes: String,
     // pub dislikes: Option<String>, TODO
     pub genre: String,
+    // info files cached before chapters are added does not have this field
+    #[serde(default)]
+    pub chapters: Vec<Chapter>,
+}
+
+/// a chapter in a video
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Chapter {
+    pub title: String,
+    /// seconds from the start of the video
+    pub start: u32,
+}
+
+impl Display for Chapter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "{} {}",
+            secs_display_string(self.start),
+            self.title
+        ))
+    }
+}
+
+impl Chapter {
+    /// characters between a chapter title and its timestamp
+    const SEPARATORS: [char; 5] = ['-', '–', '—', ':', '|'];
+
+    /// parse chapters from timestamps in the video description, such as `03:21 Intro` or
+    /// `Intro - 03:21`
+    ///
+    /// the video api of invidious has no chapters, so the description is the only source
+    // follows the same rules as YouTube: the first chapter starts at 00:00, there are at least 2
+    // chapters, and they are in ascending order
+    pub fn from_description(description: &str) -> Vec<Self> {
+        let chapters = description
+            .lines()
+            .filter_map(|line| {
+                let words = line.split_whitespace().collect::<Vec<_>>();
+                let timestamp = |word: &&str| {
+                    Self::parse_timestamp(word.trim_matches(&['(', ')', '[', ']'][..]))
+                };
+
+                // only a timestamp at the start, or at the end after a separator, so times in
+                // sentences such as `Recorded at 10:00` are not chapters
+                let (start, title) = match words.first().and_then(timestamp) {
+                    Some(start) => (start, words[1..].join(" ")),
+                    None => {
+                        let start = words.last().and_then(timestamp)?;
+                        let title = words[..words.len() - 1].join(" ");
+                        if !title.ends_with(Self::SEPARATORS) {
+                            return None;
+                        }
+                        (start, title)
+                    }
+                };
+
+                Some(Self {
+                    title: title
+                        .trim_matches(|c: char| c.is_whitespace() || Self::SEPARATORS.contains(&c))
+                        .to_string(),
+                    start,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if chapters.len() < 2
+            || chapters[0].start != 0
+            || chapters
+                .windows(2)
+                .any(|pair| pair[0].start >= pair[1].start)
+        {
+            return Vec::new();
+        }
+
+        chapters
+    }
+
+    /// parse `h:mm:ss` or `m:ss` into seconds
+    fn parse_timestamp(s: &str) -> Option<u32> {
+        let parts = s.split(':').collect::<Vec<_>>();
+        if !(2..=3).contains(&parts.len())
+            || parts
+                .iter()
+                .any(|part| part.is_empty() || !part.chars().all(|c| c.is_ascii_digit()))
+            || parts[1..].iter().any(|part| part.len() != 2)
+        {
+            return None;
+        }
+
+        parts.iter().try_fold(0_u32, |total, part| {
+            Some(total * 60 + part.parse::<u32>().ok()?)
+        })
+    }
 }
 
 /// stores information of a viewed playlist
@@ -416,6 +509,7 @@ impl Item {
 
     /// parse `Video` into `Self`
     pub fn from_full_video(original: Video, image_index: usize) -> Self {
+        let chapters = Chapter::from_description(&original.description);
         Self::FullVideo(FullVideoItem {
             title: original.title,
             id: original.id,
@@ -433,6 +527,7 @@ impl Item {
             description: original.description,
             likes: viewcount_text(original.likes as u64),
             genre: original.genre,
+            chapters,
         })
     }
 
@@ -518,3 +613,35 @@ impl Item {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn starts(description: &str) -> Vec<(u32, String)> {
+        Chapter::from_description(description)
+            .into_iter()
+            .map(|chapter| (chapter.start, chapter.title))
+            .collect()
+    }
+
+    #[test]
+    fn chapters_ignore_times_in_sentences() {
+        let description = "Recorded at 10:00 in Berlin\n\n0:00 Intro\n(1:30) Setup - part 1\nBuild | 12:05\nOutro 1:02:03\nThanks for watching!";
+        assert_eq!(
+            starts(description),
+            [
+                (0, String::from("Intro")),
+                (90, String::from("Setup - part 1")),
+                (725, String::from("Build")),
+            ]
+        );
+    }
+
+    #[test]
+    fn chapters_must_start_at_zero_and_ascend() {
+        assert!(starts("1:00 Intro\n2:00 End").is_empty());
+        assert!(starts("0:00 Intro\n2:00 Middle\n1:00 End").is_empty());
+        assert!(starts("0:00 Only one").is_empty());
+    }
+}
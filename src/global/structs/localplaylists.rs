@@ -0,0 +1,209 @@
+use crate::{
+    config::Provider,
+    global::{structs::Queue, traits::CollectionNoId},
+};
+
+use super::Item;
+use home::home_dir;
+use serde::{Deserialize, Serialize};
+use std::{error::Error, fmt::Display, fs, path::PathBuf};
+use typemap::Key;
+
+/// named playlists made by the user, stored locally and does not require an account
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct LocalPlaylists(pub Vec<LocalPlaylist>);
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LocalPlaylist {
+    /// names are unique and cannot contain spaces, as they are used in commands
+    pub name: String,
+    pub items: Vec<Item>,
+}
+
+impl Key for LocalPlaylists {
+    type Value = Self;
+}
+
+impl PartialEq for LocalPlaylist {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Display for LocalPlaylist {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({} video{})",
+            self.name,
+            self.items.len(),
+            if self.items.len() == 1 { "" } else { "s" }
+        )
+    }
+}
+
+// `Collection` only stores ids in the index and each item in `info/{id}.json`, but playlists have
+// no id and are stored in full together with their items
+impl CollectionNoId<LocalPlaylist> for LocalPlaylists {
+    const INDEX_PATH: &'static str = ".local/share/youtube-tui/playlists.json";
+
+    fn items(&self) -> &Vec<LocalPlaylist> {
+        &self.0
+    }
+
+    fn items_mut(&mut self) -> &mut Vec<LocalPlaylist> {
+        &mut self.0
+    }
+
+    fn from_items(items: Vec<LocalPlaylist>) -> Self {
+        Self(items)
+    }
+}
+
+impl LocalPlaylists {
+    pub fn get(&self, name: &str) -> Option<&LocalPlaylist> {
+        self.0.iter().find(|playlist| playlist.name == name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut LocalPlaylist> {
+        self.0.iter_mut().find(|playlist| playlist.name == name)
+    }
+
+    /// playlist names are used as file names when exporting, so they cannot be empty, start with a
+    /// `.` or contain path separators and spaces
+    pub fn validate_name(name: &str) -> Result<(), Box<dyn Error>> {
+        if name.is_empty()
+            || name.starts_with('.')
+            || name.contains(|c: char| c == '/' || c == '\\' || c.is_whitespace())
+        {
+            return Err(
+                "Playlist names cannot be empty, start with `.` or contain `/`, `\\` or spaces"
+                    .into(),
+            );
+        }
+        Ok(())
+    }
+
+    pub fn create(&mut self, name: &str) -> Result<(), Box<dyn Error>> {
+        Self::validate_name(name)?;
+        if self.get(name).is_some() {
+            return Err(format!("Playlist `{name}` already exists").into());
+        }
+
+        self.push(LocalPlaylist {
+            name: name.to_string(),
+            items: Vec::new(),
+        });
+        Ok(())
+    }
+
+    pub fn rename(&mut self, name: &str, new_name: &str) -> Result<(), Box<dyn Error>> {
+        Self::validate_name(new_name)?;
+        if self.get(new_name).is_some() {
+            return Err(format!("Playlist `{new_name}` already exists").into());
+        }
+
+        self.get_mut(name)
+            .ok_or_else(|| format!("Playlist `{name}` does not exist"))?
+            .name = new_name.to_string();
+        Ok(())
+    }
+
+    /// moves thumbnails of items in playlists from cache back to storage when exiting, so that
+    /// thumbnails can be viewed offline
+    pub fn exit_move(&self) {
+        let home_dir = home_dir().unwrap();
+        let store_thumbnails_path = home_dir.join(".local/share/youtube-tui/thumbnails/");
+        let cache_thumbnails_path = home_dir.join(".cache/youtube-tui/thumbnails/");
+
+        self.0
+            .iter()
+            .flat_map(|playlist| playlist.items.iter())
+            .filter_map(Item::id)
+            .for_each(|id| {
+                let _ = fs::rename(
+                    cache_thumbnails_path.join(id),
+                    store_thumbnails_path.join(id),
+                );
+            });
+    }
+
+    pub fn delete(&mut self, name: &str) -> Result<(), Box<dyn Error>> {
+        let index = self
+            .0
+            .iter()
+            .position(|playlist| playlist.name == name)
+            .ok_or_else(|| format!("Playlist `{name}` does not exist"))?;
+        self.0.remove(index);
+        Ok(())
+    }
+}
+
+impl LocalPlaylist {
+    /// add items to the end of the playlist, items already in the playlist are skipped, returns
+    /// the number of items added
+    pub fn add(&mut self, items: Vec<Item>) -> usize {
+        let len = self.items.len();
+        for item in items {
+            if item
+                .id()
+                .is_some_and(|id| self.items.iter().all(|existing| existing.id() != Some(id)))
+            {
+                self.items.push(item);
+            }
+        }
+        self.items.len() - len
+    }
+
+    /// index of an item by its position (starting from 1) or id
+    pub fn position(&self, identifier: &str) -> Option<usize> {
+        match identifier.parse::<usize>() {
+            Ok(position) if position != 0 && position <= self.items.len() => Some(position - 1),
+            _ => self
+                .items
+                .iter()
+                .position(|item| item.id() == Some(identifier)),
+        }
+    }
+
+    /// moves an item from one index to another, returns false if any of the index is out of bound
+    pub fn r#move(&mut self, from: usize, to: usize) -> bool {
+        if from >= self.items.len() || to >= self.items.len() {
+            return false;
+        }
+
+        let item = self.items.remove(from);
+        self.items.insert(to, item);
+        true
+    }
+
+    /// the playlist as an extended M3U playlist
+    pub fn to_m3u(&self, provider: Provider, invidious_instance: &str) -> String {
+        let mut out = format!("#EXTM3U\n#PLAYLIST:{}\n", self.name);
+        self.items
+            .iter()
+            .filter_map(|item| Some((item.id()?, item)))
+            .for_each(|(id, item)| {
+                out.push_str(&format!(
+                    "#EXTINF:-1,{item}\n{}\n",
+                    Queue::url(id, provider, invidious_instance)
+                ))
+            });
+        out
+    }
+
+    /// write the playlist to `~/.local/share/youtube-tui/saved/{name}.m3u`
+    pub fn export(
+        &self,
+        provider: Provider,
+        invidious_instance: &str,
+    ) -> Result<PathBuf, Box<dyn Error>> {
+        // playlists saved before names were validated may still have an unsafe name
+        LocalPlaylists::validate_name(&self.name)?;
+        let path = home_dir()
+            .unwrap()
+            .join(format!(".local/share/youtube-tui/saved/{}.m3u", self.name));
+        fs::write(&path, self.to_m3u(provider, invidious_instance))?;
+        Ok(path)
+    }
+}
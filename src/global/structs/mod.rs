@@ -1,30 +1,58 @@
 //! enums and structs
+mod captions;
+mod comments;
+mod downloads;
 mod errors;
 mod history;
+mod historyfile;
+mod instances;
 mod invidiousclient;
 mod item;
 mod keyaction;
 mod library;
+mod localplaylists;
 mod message;
 #[cfg(feature = "mpv")]
 mod mpv;
+mod offlineindex;
 mod page;
+mod positions;
+mod publicinstances;
+mod queue;
+mod responsecache;
+mod searchsuggestions;
 mod state_env;
 mod status;
 mod subscriptions;
+mod subscriptionsfile;
+mod syncstatus;
 mod tasks;
 
+pub use captions::*;
+pub use comments::*;
+pub use downloads::*;
 pub use errors::*;
 pub use history::*;
+pub use historyfile::*;
+pub use instances::*;
 pub use invidiousclient::*;
 pub use item::*;
 pub use keyaction::*;
 pub use library::*;
+pub use localplaylists::*;
 pub use message::*;
 #[cfg(feature = "mpv")]
 pub use mpv::*;
+pub use offlineindex::*;
 pub use page::*;
+pub use positions::*;
+pub use publicinstances::*;
+pub use queue::*;
+pub use responsecache::*;
+pub use searchsuggestions::*;
 pub use state_env::*;
 pub use status::*;
 pub use subscriptions::*;
+pub use subscriptionsfile::*;
+pub use syncstatus::*;
 pub use tasks::*;
@@ -1,10 +1,44 @@
-use std::{sync::mpsc, thread};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
-use libmpv::Mpv;
+use libmpv::{
+    events::{Event, PropertyData},
+    Format, Mpv,
+};
 use typemap::Key;
 
+/// properties observed by the event listener, the index is used as the reply id
+const OBSERVED_PROPERTIES: [(&str, Format); 9] = [
+    ("media-title", Format::String),
+    ("pause", Format::Flag),
+    ("playlist-pos", Format::Int64),
+    ("time-pos", Format::Double),
+    ("duration", Format::String),
+    ("volume", Format::String),
+    ("speed", Format::String),
+    ("loop-file", Format::String),
+    ("loop-playlist", Format::String),
+];
+
+/// observed properties that are only kept in `PlayerInfo.properties` for displaying
+const DISPLAYED_PROPERTIES: [&str; 5] =
+    ["duration", "volume", "speed", "loop-file", "loop-playlist"];
+
+/// how long (in seconds) the event listener waits for an event before checking if it should stop
+const EVENT_WAIT_TIMEOUT: f64 = 1.0;
+
 pub struct MpvWrapper {
     pub sender: mpsc::Sender<MpvAction>,
+    /// events from the mpv thread, should be handled by the main thread with `mpv_event()`
+    pub events: mpsc::Receiver<MpvEvent>,
+    /// updated by `mpv_event()`
+    pub player: PlayerInfo,
     // pub copier: mpsc::Receiver<MpvResponse>,
 }
 
@@ -23,45 +57,78 @@ impl Clone for MpvWrapper {
 impl MpvWrapper {
     pub fn spawn() -> Self {
         let (sender, receiver) = mpsc::channel();
+        let (event_sender, events) = mpsc::channel();
         // let (responder, copier) = mpsc::channel();
         thread::spawn(move || {
             let mpv = Mpv::new().unwrap();
             mpv.set_property("video", "no").unwrap();
-            loop {
-                let action = match receiver.recv() {
-                    Ok(res) => res,
-                    Err(_) => continue,
-                };
-                match action {
-                    MpvAction::Command {
-                        name,
-                        args,
-                        responder,
-                    } => match mpv
-                        .command(&name, &args.iter().map(|s| s.as_str()).collect::<Vec<_>>())
-                    {
-                        Ok(_) => responder.send(MpvResponse::Copy).unwrap(),
-                        Err(e) => responder.send(MpvResponse::Error(e.to_string())).unwrap(),
-                    },
-                    MpvAction::GetProperty { name, responder } => responder
-                        .send(MpvResponse::Property(
-                            mpv.get_property::<String>(&name).ok(),
-                        ))
-                        .unwrap(),
-                    MpvAction::SetProperty {
-                        name,
-                        value,
-                        responder,
-                    } => responder
-                        .send(match mpv.set_property(&name, value) {
-                            Ok(_) => MpvResponse::Copy,
-                            Err(e) => MpvResponse::Error(e.to_string()),
-                        })
-                        .unwrap(),
-                }
+
+            let mut event_context = mpv.create_event_context();
+            let _ = event_context.disable_deprecated_events();
+            for (id, (name, format)) in OBSERVED_PROPERTIES.into_iter().enumerate() {
+                let _ = event_context.observe_property(name, format, id as u64);
             }
+            let stopped = AtomicBool::new(false);
+
+            thread::scope(|scope| {
+                // events are waited for in their own thread, so commands are never delayed by it
+                scope.spawn(|| {
+                    let mut state = EventState::default();
+                    while !stopped.load(Ordering::Relaxed) {
+                        let event = match event_context.wait_event(EVENT_WAIT_TIMEOUT) {
+                            Some(Ok(Event::Shutdown)) => break,
+                            Some(event) => event,
+                            None => continue,
+                        };
+                        if let Some(event) = MpvEvent::from_event(event, &mut state) {
+                            if event_sender.send(event).is_err() {
+                                break;
+                            }
+                        }
+                    }
+                });
+
+                // the mpv thread stops once the wrapper is dropped
+                while let Ok(action) = receiver.recv() {
+                    Self::handle(&mpv, action);
+                }
+                stopped.store(true, Ordering::Relaxed);
+            });
         });
-        Self { sender }
+        Self {
+            sender,
+            events,
+            player: PlayerInfo::default(),
+        }
+    }
+
+    /// runs an action in the mpv thread and sends back the response
+    fn handle(mpv: &Mpv, action: MpvAction) {
+        match action {
+            MpvAction::Command {
+                name,
+                args,
+                responder,
+            } => match mpv.command(&name, &args.iter().map(|s| s.as_str()).collect::<Vec<_>>()) {
+                Ok(_) => responder.send(MpvResponse::Copy).unwrap(),
+                Err(e) => responder.send(MpvResponse::Error(e.to_string())).unwrap(),
+            },
+            MpvAction::GetProperty { name, responder } => responder
+                .send(MpvResponse::Property(
+                    mpv.get_property::<String>(&name).ok(),
+                ))
+                .unwrap(),
+            MpvAction::SetProperty {
+                name,
+                value,
+                responder,
+            } => responder
+                .send(match mpv.set_property(&name, value) {
+                    Ok(_) => MpvResponse::Copy,
+                    Err(e) => MpvResponse::Error(e.to_string()),
+                })
+                .unwrap(),
+        }
     }
 
     pub fn command(&self, name: String, args: Vec<String>) -> MpvResponse {
@@ -132,6 +199,97 @@ pub enum MpvAction {
     },
 }
 
+/// events sent by the mpv thread, converted from libmpv events
+#[derive(Debug, Clone)]
+pub enum MpvEvent {
+    MediaTitle(String),
+    Pause(bool),
+    /// -1 if nothing in the playlist is playing
+    PlaylistPos(i64),
+    TimePos(f64),
+    /// one of `DISPLAYED_PROPERTIES` changed
+    Property {
+        name: String,
+        value: String,
+    },
+    /// a file is loaded and starts playing
+    FileLoaded,
+    /// a file is played to the end, stopped or replaced
+    EndFile,
+    /// a file failed to play
+    Error(String),
+}
+
+/// kept by the event listener between events
+#[derive(Default)]
+struct EventState {
+    /// time-pos changes every frame, only send it once every second
+    last_second: Option<i64>,
+    /// the initial pause state is sent when the property is first observed, and is not worth
+    /// sending if nothing is loaded
+    loaded: bool,
+}
+
+impl MpvEvent {
+    fn from_event(event: Result<Event, libmpv::Error>, state: &mut EventState) -> Option<Self> {
+        match event {
+            Ok(Event::PropertyChange { name, change, .. }) => match (name, change) {
+                ("media-title", PropertyData::Str(title)) => {
+                    Some(Self::MediaTitle(title.to_string()))
+                }
+                ("pause", PropertyData::Flag(paused)) if state.loaded => Some(Self::Pause(paused)),
+                ("playlist-pos", PropertyData::Int64(pos)) => Some(Self::PlaylistPos(pos)),
+                ("time-pos", PropertyData::Double(time)) => {
+                    if state.last_second == Some(time as i64) {
+                        return None;
+                    }
+                    state.last_second = Some(time as i64);
+                    Some(Self::TimePos(time))
+                }
+                (name, PropertyData::Str(value)) if DISPLAYED_PROPERTIES.contains(&name) => {
+                    Some(Self::Property {
+                        name: name.to_string(),
+                        value: value.to_string(),
+                    })
+                }
+                _ => None,
+            },
+            Ok(Event::FileLoaded) => {
+                state.loaded = true;
+                Some(Self::FileLoaded)
+            }
+            Ok(Event::EndFile(_)) => {
+                state.loaded = false;
+                Some(Self::EndFile)
+            }
+            Err(e) => Some(Self::Error(e.to_string())),
+            _ => None,
+        }
+    }
+}
+
+/// info of the player kept from events, so that it is not requested from mpv on every render
+#[derive(Default)]
+pub struct PlayerInfo {
+    /// path of the loaded file, `None` if nothing is loaded
+    pub path: Option<String>,
+    pub media_title: String,
+    /// `(start time, title)` of chapters in the loaded file
+    pub chapters: Vec<(f64, String)>,
+    /// values of `DISPLAYED_PROPERTIES` as strings
+    pub properties: HashMap<String, String>,
+}
+
+impl PlayerInfo {
+    /// an observed property parsed as a number, 0 if it is not available
+    pub fn float_property(&self, name: &str) -> f64 {
+        self.properties
+            .get(name)
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
 pub enum MpvResponse {
     Copy,
     Property(Option<String>),
@@ -0,0 +1,278 @@
+use crate::global::{
+    functions::{is_partial, saved_id, size_text},
+    structs::{Downloads, Library},
+};
+use home::home_dir;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashSet},
+    error::Error,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+use typemap::Key;
+
+/// files in `save-path` of items in library, saved to `~/.local/share/youtube-tui/offline.json`
+#[derive(Clone, Default)]
+pub struct OfflineIndex {
+    pub entries: BTreeMap<String, OfflineEntry>,
+    /// partial files listed by the last `library verify`, only these are deleted by
+    /// `library verify fix`, so nothing is deleted without the user seeing it first
+    pub listed_partial: Vec<PathBuf>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct OfflineEntry {
+    pub path: PathBuf,
+    /// file extension, or `playlist` for playlist folders
+    pub format: String,
+    /// size in bytes, for playlists it is the size of all files in the folder
+    pub size: u64,
+    /// the file was indexed before but can no longer be found
+    #[serde(default)]
+    pub missing: bool,
+}
+
+/// result of scanning `save-path`
+#[derive(Default)]
+pub struct VerifyReport {
+    pub saved: usize,
+    pub size: u64,
+    /// ids of indexed items with their files removed
+    pub missing: Vec<String>,
+    /// saved files of items that are not in library, and partial files that are not from a
+    /// download job, they may be downloaded by hand so they are only reported
+    pub orphaned: Vec<PathBuf>,
+    /// files left behind by download jobs that are no longer running
+    pub partial: Vec<PathBuf>,
+}
+
+impl Key for OfflineIndex {
+    type Value = Self;
+}
+
+impl Display for OfflineEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.missing {
+            f.write_str("Missing")
+        } else {
+            write!(f, "Offline {} {}", self.format, size_text(self.size))
+        }
+    }
+}
+
+impl Display for VerifyReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} saved ({}), {} missing, {} orphaned, {} partial",
+            self.saved,
+            size_text(self.size),
+            self.missing.len(),
+            self.orphaned.len(),
+            self.partial.len()
+        )?;
+
+        if !self.missing.is_empty() {
+            write!(f, " | Missing: {}", self.missing.join(", "))?;
+        }
+
+        for (label, paths) in [("Orphaned", &self.orphaned), ("Partial", &self.partial)] {
+            if !paths.is_empty() {
+                write!(
+                    f,
+                    " | {label}: {}",
+                    paths
+                        .iter()
+                        .filter_map(|path| path.file_name()?.to_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl VerifyReport {
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty() && self.orphaned.is_empty() && self.partial.is_empty()
+    }
+}
+
+impl OfflineEntry {
+    fn from_path(path: PathBuf) -> Self {
+        let (format, size) = if path.is_dir() {
+            (
+                String::from("playlist"),
+                fs::read_dir(&path)
+                    .map(|entries| {
+                        entries
+                            .filter_map(|entry| entry.ok()?.metadata().ok())
+                            .map(|metadata| metadata.len())
+                            .sum()
+                    })
+                    .unwrap_or_default(),
+            )
+        } else {
+            (
+                path.extension()
+                    .and_then(|extension| extension.to_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                fs::metadata(&path)
+                    .map(|metadata| metadata.len())
+                    .unwrap_or_default(),
+            )
+        };
+
+        Self {
+            path,
+            format,
+            size,
+            missing: false,
+        }
+    }
+}
+
+impl OfflineIndex {
+    const PATH: &'static str = ".local/share/youtube-tui/offline.json";
+
+    pub fn load() -> Self {
+        Self {
+            entries: fs::read_to_string(home_dir().unwrap().join(Self::PATH))
+                .ok()
+                .and_then(|content| serde_json::from_str(&content).ok())
+                .unwrap_or_default(),
+            listed_partial: Vec::new(),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        let save_string = serde_json::to_string(&self.entries)?;
+
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(home_dir().unwrap().join(Self::PATH))?;
+
+        file.write_all(save_string.as_bytes())?;
+
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> Option<&OfflineEntry> {
+        self.entries.get(id)
+    }
+
+    /// rebuild the index from files in `save_path`, partial files of unfinished download jobs
+    /// are left out of the report
+    pub fn scan(
+        &mut self,
+        save_path: &Path,
+        library: &Library,
+        downloads: &Downloads,
+    ) -> Result<VerifyReport, Box<dyn Error>> {
+        let downloading = downloads.active_ids();
+        let library_ids = library
+            .0
+            .iter()
+            .filter_map(|item| item.id())
+            .collect::<HashSet<_>>();
+        let mut report = VerifyReport::default();
+        let mut entries = BTreeMap::new();
+
+        for path in fs::read_dir(save_path)?.filter_map(|entry| Some(entry.ok()?.path())) {
+            // files without an id are not created by the downloader, so they are left alone
+            let id = match saved_id(&path) {
+                Some(id) => id.to_string(),
+                None => continue,
+            };
+
+            if is_partial(&path) {
+                if downloading.contains(&id.as_str()) {
+                    continue;
+                }
+
+                if downloads.jobs.iter().any(|job| job.id == id) {
+                    report.partial.push(path);
+                } else {
+                    report.orphaned.push(path);
+                }
+                continue;
+            }
+
+            if !library_ids.contains(id.as_str()) {
+                report.orphaned.push(path);
+                continue;
+            }
+
+            let entry = OfflineEntry::from_path(path);
+            report.saved += 1;
+            report.size += entry.size;
+            entries.insert(id, entry);
+        }
+
+        // items that were indexed before stays in the index until fixed, so they can be marked
+        // as missing
+        for (id, mut entry) in std::mem::take(&mut self.entries) {
+            if entries.contains_key(&id) || !library_ids.contains(id.as_str()) {
+                continue;
+            }
+
+            entry.missing = true;
+            report.missing.push(id.clone());
+            entries.insert(id, entry);
+        }
+
+        self.entries = entries;
+        Ok(report)
+    }
+
+    /// remove missing items from index (they stay in library), and delete partial files that
+    /// were listed by the last `library verify`, returns the number of files deleted
+    ///
+    /// orphaned files are never deleted
+    pub fn fix(&mut self, report: &VerifyReport) -> Result<usize, Box<dyn Error>> {
+        report.missing.iter().for_each(|id| {
+            self.entries.remove(id);
+        });
+
+        let listed = std::mem::take(&mut self.listed_partial);
+        let mut deleted = 0;
+        for path in report.partial.iter().filter(|path| listed.contains(path)) {
+            fs::remove_file(path)?;
+            deleted += 1;
+        }
+
+        Ok(deleted)
+    }
+
+    /// delete the saved file (or folder) of an item, files are looked up by id in `save_path`,
+    /// so items saved without being in library can be deleted too
+    pub fn delete(&mut self, id: &str, save_path: &Path) -> Result<(), Box<dyn Error>> {
+        let indexed = self.entries.remove(id).is_some();
+        let paths = fs::read_dir(save_path)?
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|path| saved_id(path) == Some(id) && !is_partial(path))
+            .collect::<Vec<_>>();
+
+        if paths.is_empty() && !indexed {
+            return Err("Item is not saved".into());
+        }
+
+        for path in paths {
+            if path.is_dir() {
+                fs::remove_dir_all(path)?;
+            } else {
+                fs::remove_file(path)?;
+            }
+        }
+
+        Ok(())
+    }
+}
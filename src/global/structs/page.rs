@@ -9,11 +9,20 @@ use typemap::Key;
 #[derive(Clone, PartialEq, Eq)]
 pub enum Page {
     MainMenu(MainMenuPage),
-    // Option<new channel selected index>
-    Feed,
+    /// subscription feed, with the group of channels to show (all channels if `None`)
+    Feed(Option<String>),
+    /// videos of all subscriptions in a single list, newest first, with the page number
+    Timeline(usize),
     Search(Search),
     SingleItem(SingleItemPage),
     ChannelDisplay(ChannelDisplayPage),
+    Queue,
+    NowPlaying,
+    Downloads,
+    /// list of local playlists
+    LocalPlaylists,
+    /// items in a local playlist, with the name of the playlist
+    LocalPlaylist(String),
 }
 
 impl Debug for Page {
@@ -22,16 +31,33 @@ impl Debug for Page {
             "{}(_)",
             match self {
                 Self::MainMenu(_) => "MainMenu",
-                Self::Feed => "Feed",
+                Self::Feed(_) => "Feed",
+                Self::Timeline(_) => "Timeline",
                 Self::Search(_) => "Search",
                 Self::SingleItem(_) => "SingleItem",
                 Self::ChannelDisplay(_) => "ChannelDisplay",
+                Self::Queue => "Queue",
+                Self::NowPlaying => "NowPlaying",
+                Self::Downloads => "Downloads",
+                Self::LocalPlaylists => "LocalPlaylists",
+                Self::LocalPlaylist(_) => "LocalPlaylist",
             }
         ))
     }
 }
 
 impl Page {
+    /// pages that load items from the invidious instance
+    pub fn uses_invidious(&self) -> bool {
+        matches!(
+            self,
+            Self::MainMenu(MainMenuPage::Trending | MainMenuPage::Popular)
+                | Self::Search(_)
+                | Self::SingleItem(_)
+                | Self::ChannelDisplay(_)
+        )
+    }
+
     pub fn channeldisplay(&self) -> &ChannelDisplayPage {
         if let Self::ChannelDisplay(channeldisplaypage) = self {
             channeldisplaypage
@@ -92,10 +118,16 @@ impl Page {
         let pages_config = framework.data.global.get::<PagesConfig>().unwrap();
         match self {
             Self::MainMenu(_) => pages_config.main_menu.clone(),
-            Self::Feed => pages_config.feed.clone(),
+            Self::Feed(_) => pages_config.feed.clone(),
+            Self::Timeline(_) => pages_config.timeline.clone(),
             Self::Search(_) => pages_config.search.clone(),
             Self::SingleItem(_) => pages_config.singleitem.clone(),
             Self::ChannelDisplay(_) => pages_config.channeldisplay.clone(),
+            Self::Queue => pages_config.queue.clone(),
+            Self::NowPlaying => pages_config.now_playing.clone(),
+            Self::Downloads => pages_config.downloads.clone(),
+            Self::LocalPlaylists => pages_config.local_playlists.clone(),
+            Self::LocalPlaylist(_) => pages_config.local_playlist.clone(),
         }
     }
 
@@ -104,10 +136,16 @@ impl Page {
         let pages_config = framework.data.global.get::<PagesConfig>().unwrap();
         match self {
             Self::MainMenu(_) => pages_config.main_menu.message.clone(),
-            Self::Feed => pages_config.feed.message.clone(),
+            Self::Feed(_) => pages_config.feed.message.clone(),
+            Self::Timeline(_) => pages_config.timeline.message.clone(),
             Self::Search(_) => pages_config.search.message.clone(),
             Self::SingleItem(_) => pages_config.singleitem.message.clone(),
             Self::ChannelDisplay(_) => pages_config.channeldisplay.message.clone(),
+            Self::Queue => pages_config.queue.message.clone(),
+            Self::NowPlaying => pages_config.now_playing.message.clone(),
+            Self::Downloads => pages_config.downloads.message.clone(),
+            Self::LocalPlaylists => pages_config.local_playlists.message.clone(),
+            Self::LocalPlaylist(_) => pages_config.local_playlist.message.clone(),
         }
     }
 }
@@ -0,0 +1,161 @@
+use home::home_dir;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fs::{self, OpenOptions},
+    io::Write,
+};
+use typemap::Key;
+
+#[cfg(feature = "mpv")]
+use super::MpvWrapper;
+
+/// playback positions of videos played in the embedded mpv, indexed by video id
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Positions {
+    pub positions: HashMap<String, Position>,
+    /// id of the video currently loaded in mpv, used to detect when a new file is loaded
+    #[serde(skip)]
+    pub current: Option<String>,
+    /// video id and the position to start from when it is loaded, takes priority over the saved
+    /// position
+    #[serde(skip)]
+    pub start: Option<(String, f64)>,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Position {
+    /// seconds into the video
+    pub time: f64,
+    /// length of the video in seconds
+    pub duration: f64,
+}
+
+impl Key for Positions {
+    type Value = Self;
+}
+
+impl Position {
+    /// how much of the video is watched, from 0 to 100
+    pub fn percentage(&self) -> u8 {
+        if self.duration <= 0.0 {
+            return 0;
+        }
+
+        (self.time / self.duration * 100.0).clamp(0.0, 100.0) as u8
+    }
+
+    /// if a position is worth resuming from, videos that are barely started or already finished
+    /// starts from the beginning
+    pub fn resumable(&self) -> bool {
+        self.time > 5.0 && self.time < self.duration - 10.0
+    }
+}
+
+impl Positions {
+    const PATH: &'static str = ".local/share/youtube-tui/positions.json";
+
+    pub fn get(&self, id: &str) -> Option<&Position> {
+        self.positions.get(id)
+    }
+
+    /// loads positions from file, returns default if the file does not exist or is invalid
+    pub fn load() -> Self {
+        fs::read_to_string(home_dir().unwrap().join(Self::PATH))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        let save_string = serde_json::to_string(&self)?;
+
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(home_dir().unwrap().join(Self::PATH))?;
+
+        file.write_all(save_string.as_bytes())?;
+
+        Ok(())
+    }
+
+    /// gets the video id from a url or a path of a saved file
+    // urls can be `youtu.be/{id}`, `/watch?v={id}` or `/embed/{id}`
+    // saved files are named `{title}[{id}].{ext}`
+    pub fn id_from_path(path: &str) -> Option<String> {
+        let path = path.trim_matches('\'');
+
+        for pattern in ["watch?v=", "youtu.be/", "/embed/"] {
+            if let Some(index) = path.find(pattern) {
+                let id = path[index + pattern.len()..]
+                    .chars()
+                    .take_while(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+                    .collect::<String>();
+                return if id.is_empty() { None } else { Some(id) };
+            }
+        }
+
+        let start = path.rfind('[')?;
+        let end = start + path[start..].find(']')?;
+        Some(path[start + 1..end].to_string())
+    }
+}
+
+#[cfg(feature = "mpv")]
+impl Positions {
+    /// records the position of the currently playing video, and seeks to the previously recorded
+    /// position if a new video has been loaded
+    pub fn update(&mut self, mpv: &MpvWrapper, resume: bool) {
+        let id = match mpv
+            .property(String::from("path"))
+            .as_deref()
+            .and_then(Self::id_from_path)
+        {
+            Some(id) => id,
+            None => {
+                self.current = None;
+                return;
+            }
+        };
+
+        // both are unavaliable while the file is still loading, seeking only works after that
+        let (time, duration) = match (
+            mpv.property(String::from("time-pos"))
+                .and_then(|time| time.parse::<f64>().ok()),
+            mpv.property(String::from("duration"))
+                .and_then(|duration| duration.parse::<f64>().ok()),
+        ) {
+            (Some(time), Some(duration)) => (time, duration),
+            _ => return,
+        };
+
+        // a new video is loaded
+        if self.current.as_ref() != Some(&id) {
+            self.current = Some(id.clone());
+            let _ = self.save();
+
+            if let Some((_, start)) = self.start.take().filter(|(start_id, _)| start_id == &id) {
+                mpv.command(
+                    String::from("seek"),
+                    vec![start.to_string(), String::from("absolute")],
+                );
+                return;
+            }
+
+            if let Some(position) = self.positions.get(&id) {
+                if resume && position.resumable() {
+                    mpv.command(
+                        String::from("seek"),
+                        vec![position.time.to_string(), String::from("absolute")],
+                    );
+                    return;
+                }
+            }
+        }
+
+        self.positions.insert(id, Position { time, duration });
+    }
+}
@@ -0,0 +1,258 @@
+use super::{instances::check, InstanceHealth};
+use crate::config::MainConfig;
+use serde_json::Value;
+use std::{error::Error, fmt::Display, thread, time::Duration};
+
+/// public instances index, the healthiest instances first
+pub const PUBLIC_INSTANCES_URL: &str = "https://api.invidious.io/instances.json?sort_by=health";
+
+/// number of discovered instances saved as fallbacks, besides the one in use
+const DISCOVERED_FALLBACKS: usize = 3;
+
+/// an instance listed in the public instances index
+#[derive(Clone)]
+pub struct PublicInstance {
+    pub url: String,
+    /// country code, e.g. `DE`
+    pub region: String,
+    /// if the api is enabled, `None` if unknown
+    pub api: Option<bool>,
+    /// `https`, `onion` or `i2p`
+    pub kind: String,
+    /// uptime in the last 30 days (in percent)
+    pub uptime: Option<f32>,
+    /// if the instance is down right now
+    pub down: bool,
+}
+
+/// result of `instance discover`
+pub struct Discovered {
+    /// healthy instances with their response times, fastest first
+    pub instances: Vec<(String, Duration)>,
+    /// number of instances in the index
+    pub listed: usize,
+}
+
+impl PublicInstance {
+    /// parse the index from `PUBLIC_INSTANCES_URL`, an array of `[name, details]` pairs, entries
+    /// without an url are skipped
+    pub fn parse_index(json: &str) -> Result<Vec<Self>, Box<dyn Error>> {
+        let entries: Vec<Value> = serde_json::from_str(json)?;
+
+        Ok(entries
+            .iter()
+            .filter_map(|entry| {
+                let details = entry.get(1)?;
+                let monitor = details.get("monitor").filter(|monitor| monitor.is_object());
+
+                Some(Self {
+                    url: details
+                        .get("uri")?
+                        .as_str()?
+                        .trim_end_matches('/')
+                        .to_string(),
+                    region: details
+                        .get("region")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    api: details.get("api").and_then(Value::as_bool),
+                    kind: details
+                        .get("type")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    uptime: monitor
+                        .and_then(|monitor| monitor.get("30dRatio")?.get("ratio"))
+                        .and_then(|ratio| match ratio {
+                            Value::String(s) => s.parse().ok(),
+                            Value::Number(number) => number.as_f64().map(|number| number as f32),
+                            _ => None,
+                        }),
+                    down: monitor
+                        .and_then(|monitor| monitor.get("statusClass"))
+                        .and_then(Value::as_str)
+                        == Some("danger"),
+                })
+            })
+            .collect())
+    }
+
+    /// returns true if the instance is worth checking: reachable over https, with the api
+    /// enabled, not down, and in the region (if any)
+    ///
+    /// instances without uptime stats are skipped as they are not monitored
+    pub fn usable(&self, region: Option<&str>, min_uptime: f32) -> bool {
+        self.kind == "https"
+            && self.api == Some(true)
+            && !self.down
+            && self.uptime.is_some_and(|uptime| uptime >= min_uptime)
+            && region
+                .into_iter()
+                .all(|region| self.region.eq_ignore_ascii_case(region))
+    }
+}
+
+impl Discovered {
+    /// fetch the public instances index and check the health of all usable instances
+    pub fn fetch(region: Option<&str>, mainconfig: &MainConfig) -> Result<Self, Box<dyn Error>> {
+        let res = invidious::functions::httpreq_get(PUBLIC_INSTANCES_URL)?;
+        let body: &[u8] = res.as_ref();
+        let listed = PublicInstance::parse_index(&String::from_utf8_lossy(body))?;
+        let timeout = Duration::from_secs(mainconfig.instances.check_timeout_secs);
+
+        let handles = listed
+            .iter()
+            .filter(|instance| instance.usable(region, mainconfig.instances.discover_min_uptime))
+            .map(|instance| {
+                let url = instance.url.clone();
+                thread::spawn(move || {
+                    let health = check(&url, timeout);
+                    (url, health)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut instances = handles
+            .into_iter()
+            .filter_map(|handle| match handle.join().ok()? {
+                (url, InstanceHealth::Healthy(latency)) => Some((url, latency)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        instances.sort_by_key(|(_, latency)| *latency);
+
+        Ok(Self {
+            instances,
+            listed: listed.len(),
+        })
+    }
+
+    /// use the fastest instance as `invidious_instance` and the next fastest ones as fallbacks,
+    /// instances used before are kept as fallbacks after them
+    ///
+    /// returns false if there are no healthy instances
+    pub fn apply(&self, mainconfig: &mut MainConfig) -> bool {
+        let chosen = match self.instances.first() {
+            Some((url, _)) => url,
+            None => return false,
+        };
+
+        let mut fallbacks: Vec<String> = Vec::new();
+        for url in self.instances[1..]
+            .iter()
+            .take(DISCOVERED_FALLBACKS)
+            .map(|(url, _)| url)
+            .chain([&mainconfig.invidious_instance])
+            .chain(mainconfig.instances.fallbacks.iter())
+        {
+            let url = url.trim_end_matches('/').to_string();
+            if url != *chosen && !fallbacks.contains(&url) {
+                fallbacks.push(url);
+            }
+        }
+
+        mainconfig.invidious_instance = chosen.clone();
+        mainconfig.instances.fallbacks = fallbacks;
+        true
+    }
+}
+
+impl Display for Discovered {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.instances.first() {
+            Some((url, latency)) => write!(
+                f,
+                "Found {} healthy instances out of {} listed, using {url} ({}ms)",
+                self.instances.len(),
+                self.listed,
+                latency.as_millis()
+            ),
+            None => write!(
+                f,
+                "Found no healthy instances out of {} listed",
+                self.listed
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a trimmed copy of `PUBLIC_INSTANCES_URL`
+    const INDEX: &str = include_str!("../../../tests/fixtures/instances.json");
+
+    fn find<'a>(instances: &'a [PublicInstance], url: &str) -> &'a PublicInstance {
+        instances
+            .iter()
+            .find(|instance| instance.url == url)
+            .unwrap()
+    }
+
+    #[test]
+    fn parse_index_reads_details() {
+        let instances = PublicInstance::parse_index(INDEX).unwrap();
+
+        // the entry without an uri is skipped
+        assert_eq!(instances.len(), 7);
+
+        let healthy = find(&instances, "https://inv.example.de");
+        assert_eq!(healthy.region, "DE");
+        assert_eq!(healthy.api, Some(true));
+        assert_eq!(healthy.kind, "https");
+        assert_eq!(healthy.uptime, Some(99.5));
+        assert!(!healthy.down);
+
+        // trailing slashes are trimmed
+        let no_api = find(&instances, "https://invidious.example.us");
+        assert_eq!(no_api.api, Some(false));
+
+        assert!(find(&instances, "https://down.example.fr").down);
+        assert_eq!(
+            find(&instances, "https://numeric.example.nl").uptime,
+            Some(97.3)
+        );
+
+        let unmonitored = find(&instances, "https://unmonitored.example.de");
+        assert_eq!(unmonitored.api, None);
+        assert_eq!(unmonitored.uptime, None);
+        assert!(!unmonitored.down);
+    }
+
+    #[test]
+    fn parse_index_rejects_invalid_json() {
+        assert!(PublicInstance::parse_index("{\"not\": \"an array\"}").is_err());
+    }
+
+    #[test]
+    fn usable_filters_instances() {
+        let instances = PublicInstance::parse_index(INDEX).unwrap();
+        let usable = |region: Option<&str>, min_uptime: f32| {
+            instances
+                .iter()
+                .filter(|instance| instance.usable(region, min_uptime))
+                .map(|instance| instance.url.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        // api disabled, down, low uptime, unmonitored and onion instances are skipped
+        assert_eq!(
+            usable(None, 95.0),
+            ["https://inv.example.de", "https://numeric.example.nl"]
+        );
+        assert_eq!(
+            usable(None, 80.0),
+            [
+                "https://inv.example.de",
+                "https://flaky.example.de",
+                "https://numeric.example.nl"
+            ]
+        );
+
+        // regions are case insensitive
+        assert_eq!(usable(Some("de"), 95.0), ["https://inv.example.de"]);
+        assert!(usable(Some("US"), 0.0).is_empty());
+    }
+}
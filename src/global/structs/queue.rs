@@ -0,0 +1,159 @@
+use crate::{
+    config::{MainConfig, Provider},
+    global::{functions::find_library_item, traits::Collection},
+};
+
+use super::Item;
+#[cfg(feature = "mpv")]
+use super::MpvWrapper;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+use typemap::Key;
+
+/// the play queue of the embedded mpv player
+// the order of items is kept the same as the order in the mpv playlist, but other commands can
+// modify the mpv playlist, so entries are looked up by their filename with `mpv_index`
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Queue(pub Vec<Item>);
+
+impl Key for Queue {
+    type Value = Self;
+}
+
+impl Collection<Item> for Queue {
+    const INDEX_PATH: &'static str = ".local/share/youtube-tui/queue.json";
+
+    fn items(&self) -> &Vec<Item> {
+        &self.0
+    }
+
+    fn items_mut(&mut self) -> &mut Vec<Item> {
+        &mut self.0
+    }
+
+    fn from_items(items: Vec<Item>) -> Self {
+        Self(items)
+    }
+}
+
+impl Queue {
+    /// returns the index of an item in the queue
+    pub fn position(&self, id: &str) -> Option<usize> {
+        self.0.iter().position(|item| item.id() == Some(id))
+    }
+
+    /// moves an item from one index to another, returns false if any of the index is out of bound
+    pub fn r#move(&mut self, from: usize, to: usize) -> bool {
+        if from >= self.0.len() || to >= self.0.len() {
+            return false;
+        }
+
+        let item = self.0.remove(from);
+        self.0.insert(to, item);
+        true
+    }
+
+    /// shuffles the queue, keeping the item at index `keep` (if any) as the first item
+    pub fn shuffle(&mut self, keep: Option<usize>) {
+        let kept = keep
+            .filter(|index| *index < self.0.len())
+            .map(|index| self.0.remove(index));
+
+        // xorshift seeded with the current time, good enough for shuffling a playlist
+        let mut seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos() as u64)
+            .unwrap_or(1)
+            | 1;
+        for i in (1..self.0.len()).rev() {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            self.0.swap(i, (seed % (i as u64 + 1)) as usize);
+        }
+
+        if let Some(kept) = kept {
+            self.0.insert(0, kept);
+        }
+    }
+
+    /// the url mpv should load for a queue item
+    pub fn url(id: &str, provider: Provider, invidious_instance: &str) -> String {
+        match provider {
+            Provider::YouTube => format!("https://youtu.be/{id}"),
+            Provider::Invidious => format!("{invidious_instance}/watch?v={id}"),
+        }
+    }
+
+    /// the saved file of a queue item if there is one, otherwise its url
+    pub fn source(
+        id: &str,
+        provider: Provider,
+        invidious_instance: &str,
+        mainconfig: &MainConfig,
+    ) -> String {
+        match find_library_item(id, mainconfig) {
+            Some(path) => path.to_string_lossy().to_string(),
+            None => Self::url(id, provider, invidious_instance),
+        }
+    }
+}
+
+#[cfg(feature = "mpv")]
+impl Queue {
+    /// index of the currently playing item, read back from mpv's `playlist-pos`
+    pub fn playing(&self, mpv: &MpvWrapper) -> Option<usize> {
+        let pos = mpv
+            .property("playlist-pos".to_string())?
+            .parse::<usize>()
+            .ok()?;
+        let filename = mpv.property(format!("playlist/{pos}/filename"))?;
+
+        // the mpv playlist can be modified by other commands (e.g. `Play audio` runs `mpv stop`),
+        // so double check if the entry is really the one in the queue
+        match self.0.get(pos).and_then(Item::id) {
+            Some(id) if filename.contains(id) => Some(pos),
+            _ => self
+                .0
+                .iter()
+                .position(|item| item.id().is_some_and(|id| filename.contains(id))),
+        }
+    }
+
+    /// index of an item in the mpv playlist, found by its filename as the mpv playlist can be
+    /// modified by other commands and go out of sync with the queue
+    pub fn mpv_index(mpv: &MpvWrapper, id: &str) -> Option<usize> {
+        let count = mpv
+            .property("playlist-count".to_string())?
+            .parse::<usize>()
+            .ok()?;
+        (0..count).find(|index| {
+            mpv.property(format!("playlist/{index}/filename"))
+                .is_some_and(|filename| filename.contains(id))
+        })
+    }
+
+    /// appends all items (except the first `skip` items) to the mpv playlist without playing them
+    pub fn load_into(
+        &self,
+        mpv: &MpvWrapper,
+        skip: usize,
+        provider: Provider,
+        invidious_instance: &str,
+        mainconfig: &MainConfig,
+    ) {
+        self.0
+            .iter()
+            .skip(skip)
+            .filter_map(Item::id)
+            .for_each(|id| {
+                mpv.command(
+                    String::from("loadfile"),
+                    vec![
+                        Self::source(id, provider, invidious_instance, mainconfig),
+                        String::from("append"),
+                    ],
+                );
+            });
+    }
+}
@@ -0,0 +1,389 @@
+use crate::{config::CacheConfig, global::functions::size_text};
+use chrono::Utc;
+use home::home_dir;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+use typemap::Key;
+
+/// responses from invidious saved to `~/.cache/youtube-tui/responses/`, kept across sessions
+///
+/// the index is shared so the cache can be used while other global data is borrowed
+#[derive(Clone, Default)]
+pub struct ResponseCache {
+    index: Arc<Mutex<HashMap<String, CacheEntry>>>,
+    /// offline mode, responses are only taken from the cache, even if they are expired
+    pub offline: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub kind: CacheKind,
+    /// size of the response file in bytes
+    pub size: u64,
+    /// when the response was fetched (unix timestamp)
+    pub fetched: u64,
+    /// when the response was last used, least recently used responses are removed first when the
+    /// cache is full
+    pub used: u64,
+}
+
+/// kinds of responses, each with its own ttl
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheKind {
+    Video,
+    /// channel info, videos and playlists
+    Channel,
+    Playlist,
+    Search,
+    /// trending and popular videos
+    Trending,
+}
+
+/// result of `cache stats`
+pub struct CacheStats {
+    /// number of responses of each kind
+    pub counts: Vec<(CacheKind, usize)>,
+    pub expired: usize,
+    pub size: u64,
+    pub max_size: u64,
+}
+
+impl Key for ResponseCache {
+    type Value = Self;
+}
+
+impl Display for CacheKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Video => "video",
+            Self::Channel => "channel",
+            Self::Playlist => "playlist",
+            Self::Search => "search",
+            Self::Trending => "trending",
+        })
+    }
+}
+
+impl Display for CacheStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} responses ({} of {}), {} expired | {}",
+            self.counts.iter().map(|(_, count)| count).sum::<usize>(),
+            size_text(self.size),
+            size_text(self.max_size),
+            self.expired,
+            self.counts
+                .iter()
+                .map(|(kind, count)| format!("{kind}: {count}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+impl CacheKind {
+    pub const ALL: [Self; 5] = [
+        Self::Video,
+        Self::Channel,
+        Self::Playlist,
+        Self::Search,
+        Self::Trending,
+    ];
+
+    /// kind names used in `cache clear`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "video" | "videos" => Some(Self::Video),
+            "channel" | "channels" => Some(Self::Channel),
+            "playlist" | "playlists" => Some(Self::Playlist),
+            "search" => Some(Self::Search),
+            "trending" | "popular" => Some(Self::Trending),
+            _ => None,
+        }
+    }
+
+    /// in seconds, 0 if responses of this kind are not cached
+    pub fn ttl(&self, config: &CacheConfig) -> u64 {
+        match self {
+            Self::Video => config.videos_ttl_secs,
+            Self::Channel => config.channels_ttl_secs,
+            Self::Playlist => config.playlists_ttl_secs,
+            Self::Search => config.search_ttl_secs,
+            Self::Trending => config.trending_ttl_secs,
+        }
+    }
+}
+
+impl CacheEntry {
+    pub fn expired(&self, config: &CacheConfig, now: u64) -> bool {
+        self.fetched + self.kind.ttl(config) <= now
+    }
+}
+
+impl ResponseCache {
+    const DIR: &'static str = ".cache/youtube-tui/responses/";
+    const INDEX_PATH: &'static str = ".cache/youtube-tui/responses/index.json";
+
+    /// loads the index, responses with their files removed are dropped
+    pub fn load() -> Self {
+        let index: HashMap<String, CacheEntry> =
+            fs::read_to_string(home_dir().unwrap().join(Self::INDEX_PATH))
+                .ok()
+                .and_then(|content| serde_json::from_str(&content).ok())
+                .unwrap_or_default();
+
+        Self {
+            index: Arc::new(Mutex::new(
+                index
+                    .into_iter()
+                    .filter(|(id, _)| path(id).exists())
+                    .collect(),
+            )),
+            offline: false,
+        }
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        let save_string = serde_json::to_string(&*self.index.lock().unwrap())?;
+        fs::create_dir_all(home_dir().unwrap().join(Self::DIR))?;
+
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(home_dir().unwrap().join(Self::INDEX_PATH))?;
+
+        file.write_all(save_string.as_bytes())?;
+
+        Ok(())
+    }
+
+    /// a response that is not expired, or any cached response in offline mode
+    pub fn get<T: DeserializeOwned>(
+        &self,
+        kind: CacheKind,
+        key: &str,
+        config: &CacheConfig,
+    ) -> Option<T> {
+        let id = format!("{kind}/{key}");
+        let now = Utc::now().timestamp() as u64;
+        let mut index = self.index.lock().unwrap();
+        if index.get(&id)?.expired(config, now) && !self.offline {
+            return None;
+        }
+
+        match fs::read_to_string(path(&id))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+        {
+            Some(value) => {
+                index.get_mut(&id).unwrap().used = now;
+                Some(value)
+            }
+            // the file is removed or from an older version of youtube-tui
+            None => {
+                index.remove(&id);
+                None
+            }
+        }
+    }
+
+    /// save a response, least recently used responses are removed if the cache is full
+    pub fn insert<T: Serialize>(
+        &self,
+        kind: CacheKind,
+        key: &str,
+        value: &T,
+        config: &CacheConfig,
+    ) -> Result<(), Box<dyn Error>> {
+        if kind.ttl(config) == 0 {
+            return Ok(());
+        }
+
+        let id = format!("{kind}/{key}");
+        let content = serde_json::to_string(value)?;
+        fs::create_dir_all(home_dir().unwrap().join(Self::DIR))?;
+        fs::write(path(&id), &content)?;
+
+        let now = Utc::now().timestamp() as u64;
+        {
+            let mut index = self.index.lock().unwrap();
+            index.insert(
+                id,
+                CacheEntry {
+                    kind,
+                    size: content.len() as u64,
+                    fetched: now,
+                    used: now,
+                },
+            );
+            evict(&mut index, config.max_size_mb * 1_000_000);
+        }
+
+        self.save()
+    }
+
+    /// returns the cached response if there is one, if not the response is fetched and cached
+    ///
+    /// nothing is fetched in offline mode
+    pub fn fetch<T: Serialize + DeserializeOwned>(
+        &self,
+        kind: CacheKind,
+        key: &str,
+        config: &CacheConfig,
+        fetch: impl FnOnce() -> Result<T, Box<dyn Error>>,
+    ) -> Result<T, Box<dyn Error>> {
+        if let Some(value) = self.get(kind, key, config) {
+            return Ok(value);
+        }
+        if self.offline {
+            return Err("Not cached, unavailable in offline mode".into());
+        }
+
+        let value = fetch()?;
+        // the response is still shown if it cannot be cached
+        let _ = self.insert(kind, key, &value, config);
+        Ok(value)
+    }
+
+    /// remove a single response, returns false if it is not cached
+    pub fn remove(&self, kind: CacheKind, key: &str) -> bool {
+        let id = format!("{kind}/{key}");
+        let removed = self.index.lock().unwrap().remove(&id).is_some();
+        if removed {
+            let _ = fs::remove_file(path(&id));
+            let _ = self.save();
+        }
+        removed
+    }
+
+    /// remove all responses of a kind, or all responses if no kind is given, returns the number
+    /// of responses removed
+    pub fn clear(&self, kind: Option<CacheKind>) -> usize {
+        let mut index = self.index.lock().unwrap();
+        let ids = index
+            .iter()
+            .filter(|(_, entry)| kind.into_iter().all(|kind| entry.kind == kind))
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+        remove_all(&mut index, &ids);
+        drop(index);
+
+        let _ = self.save();
+        ids.len()
+    }
+
+    /// remove expired responses, least recently used responses if the cache is full, and files
+    /// that are not in the index (e.g. left by an interrupted session), returns the number of
+    /// files removed
+    pub fn prune(&self, config: &CacheConfig) -> usize {
+        let now = Utc::now().timestamp() as u64;
+        let mut index = self.index.lock().unwrap();
+        let expired = index
+            .iter()
+            .filter(|(_, entry)| entry.expired(config, now))
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+        remove_all(&mut index, &expired);
+        let evicted = evict(&mut index, config.max_size_mb * 1_000_000);
+
+        let indexed = index.keys().map(|id| path(id)).collect::<HashSet<_>>();
+        let index_path = home_dir().unwrap().join(Self::INDEX_PATH);
+        let mut unindexed = 0;
+        for file in fs::read_dir(home_dir().unwrap().join(Self::DIR))
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|file| file.is_file() && *file != index_path && !indexed.contains(file))
+        {
+            if fs::remove_file(file).is_ok() {
+                unindexed += 1;
+            }
+        }
+        drop(index);
+
+        let _ = self.save();
+        expired.len() + evicted + unindexed
+    }
+
+    pub fn stats(&self, config: &CacheConfig) -> CacheStats {
+        let now = Utc::now().timestamp() as u64;
+        let index = self.index.lock().unwrap();
+
+        CacheStats {
+            counts: CacheKind::ALL
+                .into_iter()
+                .map(|kind| {
+                    (
+                        kind,
+                        index.values().filter(|entry| entry.kind == kind).count(),
+                    )
+                })
+                .collect(),
+            expired: index
+                .values()
+                .filter(|entry| entry.expired(config, now))
+                .count(),
+            size: index.values().map(|entry| entry.size).sum(),
+            max_size: config.max_size_mb * 1_000_000,
+        }
+    }
+}
+
+/// file of a response, keys such as search queries are hashed to get a valid file name
+fn path(id: &str) -> PathBuf {
+    home_dir().unwrap().join(format!(
+        "{}{}-{:016x}.json",
+        ResponseCache::DIR,
+        id.split('/').next().unwrap_or_default(),
+        stable_hash(id)
+    ))
+}
+
+/// 64 bit FNV-1a, unlike `DefaultHasher` it is the same across Rust versions, so responses cached
+/// by an older build are still found
+fn stable_hash(s: &str) -> u64 {
+    s.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn remove_all(index: &mut HashMap<String, CacheEntry>, ids: &[String]) {
+    ids.iter().for_each(|id| {
+        index.remove(id);
+        let _ = fs::remove_file(path(id));
+    });
+}
+
+/// remove least recently used responses until the cache is no larger than `max_size`, returns
+/// the number of responses removed
+fn evict(index: &mut HashMap<String, CacheEntry>, max_size: u64) -> usize {
+    let mut size = index.values().map(|entry| entry.size).sum::<u64>();
+    let mut entries = index
+        .iter()
+        .map(|(id, entry)| (entry.used, entry.size, id.clone()))
+        .collect::<Vec<_>>();
+    entries.sort();
+
+    let ids = entries
+        .into_iter()
+        .take_while(|(_, entry_size, _)| {
+            let over = size > max_size;
+            size = size.saturating_sub(*entry_size);
+            over
+        })
+        .map(|(_, _, id)| id)
+        .collect::<Vec<_>>();
+    remove_all(index, &ids);
+    ids.len()
+}
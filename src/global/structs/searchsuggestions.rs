@@ -0,0 +1,130 @@
+use super::SearchHistory;
+use crate::global::functions::xml_unescape;
+use serde_json::Value;
+use std::{
+    error::Error,
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::Duration,
+};
+use typemap::Key;
+use urlencoding::encode;
+
+/// suggestions are only fetched once typing pauses for this long
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// a query with its suggestions
+pub type Suggestions = (String, Vec<String>);
+
+/// autocomplete for the search bar, suggestions are fetched from invidious in a worker thread so
+/// typing is never blocked
+#[derive(Clone)]
+pub struct SearchSuggestions {
+    /// `(instance, query)` to fetch suggestions for
+    sender: mpsc::Sender<(String, String)>,
+    /// fetched suggestions with their query, should be handled by the main thread
+    pub events: Arc<Mutex<mpsc::Receiver<Suggestions>>>,
+    /// the latest fetched suggestions with their query
+    pub latest: Option<Suggestions>,
+}
+
+impl Key for SearchSuggestions {
+    type Value = Self;
+}
+
+impl Default for SearchSuggestions {
+    fn default() -> Self {
+        let (sender, requests) = mpsc::channel::<(String, String)>();
+        let (results, receiver) = mpsc::channel();
+
+        // the worker stops once the sender is dropped on exit
+        thread::spawn(move || {
+            while let Ok(mut request) = requests.recv() {
+                // skip queries typed over before the debounce ends, only the last one is fetched
+                while let Ok(newer) = requests.recv_timeout(DEBOUNCE) {
+                    request = newer;
+                }
+
+                let (instance, query) = request;
+                // suggestions are not important enough to show errors for
+                if let Ok(suggestions) = fetch(&instance, &query) {
+                    if results.send((query, suggestions)).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        Self {
+            sender,
+            events: Arc::new(Mutex::new(receiver)),
+            latest: None,
+        }
+    }
+}
+
+impl SearchSuggestions {
+    /// fetch suggestions for a query in the background, blank queries are ignored
+    pub fn request(&self, instance: &str, query: &str) {
+        let query = query.trim();
+        if !query.is_empty() {
+            let _ = self.sender.send((instance.to_string(), query.to_string()));
+        }
+    }
+
+    /// searches in history containing the query (latest first), then suggestions from invidious
+    /// if they are fetched for the same query, without duplicates
+    pub fn entries(&self, query: &str, history: &SearchHistory, max: usize) -> Vec<String> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        let lowercase = query.to_lowercase();
+        let remote = self
+            .latest
+            .iter()
+            .filter(|(latest_query, _)| latest_query == query)
+            .flat_map(|(_, suggestions)| suggestions.iter());
+
+        let mut entries: Vec<String> = Vec::new();
+        for entry in history
+            .0
+            .iter()
+            .rev()
+            .filter(|entry| entry.to_lowercase().contains(&lowercase))
+            .chain(remote)
+        {
+            if entries.len() == max {
+                break;
+            }
+            // the query itself is not worth suggesting
+            if entry != query && !entries.contains(entry) {
+                entries.push(entry.clone());
+            }
+        }
+
+        entries
+    }
+}
+
+/// request the suggestions api of an instance, which responds with
+/// `{"query": "..", "suggestions": [..]}`
+fn fetch(instance: &str, query: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    let res = invidious::functions::httpreq_get(&format!(
+        "{instance}/api/v1/search/suggestions?q={}",
+        encode(query)
+    ))?;
+    let body: &[u8] = res.as_ref();
+    let value: Value = serde_json::from_slice(body)?;
+
+    Ok(value
+        .get("suggestions")
+        .and_then(Value::as_array)
+        .ok_or("invalid response")?
+        .iter()
+        .filter_map(Value::as_str)
+        // suggestions are html escaped, e.g. `&#39;`
+        .map(xml_unescape)
+        .collect())
+}
@@ -1,21 +1,26 @@
 use super::*;
 use crate::{
-    config::SyncConfig,
+    config::{FeedFilters, SyncConfig},
     global::{
-        functions::{download_all_images, DownloadRequest},
+        functions::{
+            download_all_images, feed_or_else, httpreq_get_timeout, retry_delay, DownloadRequest,
+        },
         traits::{Collection, CollectionItem},
     },
 };
 use chrono::Utc;
 use home::home_dir;
+use invidious::{channel::Channel, hidden::ChannelVideo};
 use serde::*;
 use std::{
+    collections::VecDeque,
     error::Error,
     fmt::Display,
     fs::{self, OpenOptions},
     io::Write,
-    sync::{atomic::AtomicU32, mpsc, Arc},
+    sync::{mpsc, Arc, Mutex},
     thread,
+    time::Duration,
 };
 use typemap::Key;
 
@@ -28,134 +33,148 @@ impl Key for Subscriptions {
 }
 
 impl Subscriptions {
-    /// Res<(success, failed)>
+    /// sync all channels in the background with a pool of `syncconfig.workers` threads, each
+    /// channel is sent to `sender` once synced and should be handled with `synced()`
+    ///
+    /// videos hidden by `filters` are left out, retries go to the next instance in `instances`
     pub fn sync(
-        &mut self,
-        client: &InvidiousClient,
+        &self,
+        instances: &[String],
         image_index: usize,
         download_thumbnails: bool,
         syncconfig: SyncConfig,
-    ) -> (u32, u32, u32, u32) {
-        let failed = Arc::new(AtomicU32::new(0));
-        let success = Arc::new(AtomicU32::new(0));
-        let empty = Arc::new(AtomicU32::new(0));
-        let cached = Arc::new(AtomicU32::new(0));
-
-        let len = self.0.len();
+        filters: FeedFilters,
+        sender: mpsc::Sender<SyncedChannel>,
+    ) -> SyncProgress {
         let now = chrono::Utc::now().timestamp() as u64;
-        let (tx, rx) = mpsc::channel();
-        let mut channels = Vec::new();
-        std::mem::swap(&mut self.0, &mut channels);
-
-        channels.into_iter().for_each(|mut item| {
-            if item.last_sync > now - syncconfig.sync_videos_cooldown_secs {
-                tx.send(item).unwrap();
-                cached.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-                return;
-            }
-            let tx = tx.clone();
-            let client = client.clone();
-            let success = success.clone();
-            let failed = failed.clone();
-            let empty = empty.clone();
-            thread::spawn(move || {
-                let res = sync_one(
-                    &item.channel.id,
-                    &client,
+        let queue = self
+            .0
+            .iter()
+            .filter(|item| item.last_sync <= now - syncconfig.sync_videos_cooldown_secs)
+            .cloned()
+            .collect::<VecDeque<_>>();
+        let progress = SyncProgress {
+            total: queue.len() as u32,
+            cached: (self.0.len() - queue.len()) as u32,
+            ..Default::default()
+        };
+
+        let workers = syncconfig.workers.clamp(1, queue.len().max(1));
+        let queue = Arc::new(Mutex::new(queue));
+        for _ in 0..workers {
+            let queue = queue.clone();
+            let sender = sender.clone();
+            let instances = instances.to_vec();
+            let syncconfig = syncconfig.clone();
+            let filters = filters.clone();
+            thread::spawn(move || loop {
+                // the lock is released before syncing so other workers can take channels
+                let item = queue.lock().unwrap().pop_front();
+                let mut item = match item {
+                    Some(item) => item,
+                    None => break,
+                };
+
+                let outcome = item.sync(
+                    &instances,
                     image_index,
                     download_thumbnails,
-                    syncconfig.sync_channel_info
-                        && syncconfig.sync_channel_cooldown_secs + item.last_sync_channel < now,
+                    &syncconfig,
+                    &filters,
+                    now,
                 );
-                match res {
-                    Ok((videos, _channel)) if videos.is_empty() => {
-                        empty.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-                    }
-                    Ok((videos, channel)) => {
-                        success.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-                        // cannot just compare video publish timestamp to sync timestamp
-                        // because publish timestamp is hugely inaccurate seen here
-                        // https://github.com/iv-org/invidious/issues/570
-                        item.has_new = !videos.is_empty()
-                            && (item.videos.is_empty() || videos[0].id != item.videos[0].id);
-                        item.videos = videos;
-                        item.last_sync = now;
-
-                        if let Some(channel) = channel {
-                            item.channel = channel;
-                            item.last_sync_channel = now;
-                        }
-                    }
-                    _ => {
-                        failed.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-                    }
+                if sender.send(SyncedChannel { item, outcome }).is_err() {
+                    break;
                 }
-                tx.send(item)
             });
-        });
-
-        for item in rx.into_iter().take(len) {
-            self.0.push(item);
         }
 
-        self.0.sort();
+        progress
+    }
+
+    /// update a channel synced by `sync()`, returns true if all channels are synced
+    pub fn synced(&mut self, synced: SyncedChannel, progress: &mut SyncProgress) -> bool {
+        progress.done += 1;
+        match &synced.outcome {
+            SyncOutcome::Success(new_videos) => {
+                progress.success += 1;
+                progress.new_videos += new_videos.len() as u32;
+            }
+            SyncOutcome::Empty => progress.empty += 1,
+            SyncOutcome::Failed => progress.failed += 1,
+        }
 
-        let _ = self.save();
+        // the channel may have been unsubscribed while syncing, and only fields changed by
+        // syncing are copied, as groups may be edited and new videos marked as seen meanwhile
+        if let (Some(item), SyncOutcome::Success(_)) = (
+            self.0
+                .iter_mut()
+                .find(|item| item.channel.id == synced.item.channel.id),
+            &synced.outcome,
+        ) {
+            item.videos = synced.item.videos;
+            item.last_sync = synced.item.last_sync;
+            item.last_sync_channel = synced.item.last_sync_channel;
+            item.channel = synced.item.channel;
+            item.has_new |= synced.item.has_new;
+        }
 
-        (
-            success.load(std::sync::atomic::Ordering::Relaxed),
-            failed.load(std::sync::atomic::Ordering::Relaxed),
-            empty.load(std::sync::atomic::Ordering::Relaxed),
-            cached.load(std::sync::atomic::Ordering::Relaxed),
-        )
+        if progress.is_done() {
+            self.0.sort();
+            let _ = self.save();
+        }
+        progress.is_done()
     }
 
+    /// sync a single channel, subscribing to it if it is not subscribed, returns the new videos
     pub fn sync_one(
         &mut self,
         id: &str,
-        client: &InvidiousClient,
+        instances: &[String],
         image_index: usize,
         download_thumbnails: bool,
         syncconfig: &SyncConfig,
-    ) -> Result<(), Box<dyn Error>> {
+        filters: &FeedFilters,
+    ) -> Result<Vec<MiniVideoItem>, Box<dyn Error>> {
         let now = Utc::now().timestamp() as u64;
-        match self.0.iter_mut().find(|item| item.channel.id == id) {
+        let new_videos = match self.0.iter_mut().find(|item| item.channel.id == id) {
             Some(item) => {
-                let (videos, channel) = sync_one(
+                let (videos, channel) = sync_retrying(
                     id,
-                    client,
+                    instances,
                     image_index,
                     download_thumbnails,
                     syncconfig.sync_channel_info
                         && syncconfig.sync_channel_cooldown_secs + item.last_sync_channel < now,
+                    syncconfig,
                 )?;
-
-                item.has_new = !videos.is_empty()
-                    && (item.videos.is_empty() || videos[0].id != item.videos[0].id);
-                item.videos = videos;
-                item.last_sync = now;
-                if let Some(channel) = channel {
-                    item.channel = channel;
-                    item.last_sync_channel = now;
-                }
+                item.update(filters.apply(videos), channel, now)
             }
             None => {
-                let (videos, channel) =
-                    sync_one(id, client, image_index, download_thumbnails, true)?;
+                let (videos, channel) = sync_retrying(
+                    id,
+                    instances,
+                    image_index,
+                    download_thumbnails,
+                    true,
+                    syncconfig,
+                )?;
                 self.0.push(SubItem {
                     channel: channel.unwrap(),
-                    videos,
+                    videos: filters.apply(videos),
                     last_sync: now,
                     last_sync_channel: now,
                     has_new: true,
-                })
+                    groups: Vec::new(),
+                });
+                Vec::new()
             }
-        }
+        };
 
         self.0.sort();
 
         self.save()?;
-        Ok(())
+        Ok(new_videos)
     }
 
     pub fn remove_one(&mut self, id: &str) -> bool {
@@ -167,66 +186,172 @@ impl Subscriptions {
         false
     }
 
-    pub fn get_all_videos(&self) -> Vec<MiniVideoItem> {
+    pub fn get_all_videos(&self, group: Option<&str>) -> Vec<MiniVideoItem> {
         let mut videos = self
-            .0
-            .iter()
+            .in_group(group)
+            .into_iter()
             .flat_map(|item| item.videos.clone())
             .collect::<Vec<_>>();
         videos.sort();
         videos
     }
 
-    pub fn get_channels(&self) -> Vec<FullChannelItem> {
-        self.0.iter().map(|item| item.channel.clone()).collect()
+    /// videos of all channels in a single list, newest first
+    pub fn timeline(&self) -> Vec<MiniVideoItem> {
+        let mut videos = self
+            .0
+            .iter()
+            .flat_map(|item| item.videos.iter().cloned())
+            .collect::<Vec<_>>();
+        videos.sort_by(|a, b| b.cmp(a));
+        videos.dedup_by(|a, b| a.id == b.id);
+        videos
+    }
+
+    /// clear the new videos marker of all channels, returns the number of channels cleared
+    pub fn mark_all_seen(&mut self) -> usize {
+        self.0
+            .iter_mut()
+            .filter(|item| item.has_new)
+            .map(|item| item.has_new = false)
+            .count()
+    }
+
+    pub fn get_channels(&self, group: Option<&str>) -> Vec<FullChannelItem> {
+        self.in_group(group)
+            .into_iter()
+            .map(|item| item.channel.clone())
+            .collect()
+    }
+
+    /// channels tagged with the group, or all channels if `group` is `None`
+    pub fn in_group(&self, group: Option<&str>) -> Vec<&SubItem> {
+        self.0
+            .iter()
+            .filter(|item| match group {
+                Some(group) => item.groups.iter().any(|tagged| tagged == group),
+                None => true,
+            })
+            .collect()
+    }
+
+    /// names of all groups, sorted
+    pub fn groups(&self) -> Vec<&str> {
+        let mut groups = self
+            .0
+            .iter()
+            .flat_map(|item| item.groups.iter().map(String::as_str))
+            .collect::<Vec<_>>();
+        groups.sort();
+        groups.dedup();
+        groups
+    }
+
+    /// add a channel to a group, returns false if it is already in the group
+    pub fn tag(&mut self, id: &str, group: &str) -> Result<bool, Box<dyn Error>> {
+        let item = self
+            .0
+            .iter_mut()
+            .find(|item| item.channel.id == id)
+            .ok_or("Channel not found in subscriptions")?;
+
+        if item.groups.iter().any(|tagged| tagged == group) {
+            return Ok(false);
+        }
+        item.groups.push(group.to_string());
+        item.groups.sort();
+        Ok(true)
+    }
+
+    /// remove a channel from a group, returns false if it is not in the group
+    pub fn untag(&mut self, id: &str, group: &str) -> Result<bool, Box<dyn Error>> {
+        let item = self
+            .0
+            .iter_mut()
+            .find(|item| item.channel.id == id)
+            .ok_or("Channel not found in subscriptions")?;
+
+        let len = item.groups.len();
+        item.groups.retain(|tagged| tagged != group);
+        Ok(item.groups.len() != len)
     }
 }
 
-fn sync_one(
+/// response of `/api/v1/channels/{id}/videos`
+#[derive(Deserialize)]
+struct ChannelVideos {
+    videos: Vec<ChannelVideo>,
+}
+
+/// sync a channel with the timeout and retries set in `syncconfig`, each retry uses the next
+/// instance in `instances`
+fn sync_retrying(
     id: &str,
-    client: &InvidiousClient,
+    instances: &[String],
     image_index: usize,
     download_thumbnails: bool,
     sync_channel_info: bool,
+    syncconfig: &SyncConfig,
 ) -> Result<(Vec<MiniVideoItem>, Option<FullChannelItem>), Box<dyn Error>> {
-    let (tx, rx) = mpsc::channel();
+    let mut attempt = 0;
+    loop {
+        let e = match sync_one(
+            id,
+            &instances[attempt as usize % instances.len()],
+            image_index,
+            download_thumbnails,
+            sync_channel_info,
+            syncconfig.feed_url(),
+            Duration::from_secs(syncconfig.timeout_secs),
+        ) {
+            Ok(res) => return Ok(res),
+            Err(e) => e,
+        };
 
-    if sync_channel_info {
-        let client2 = client.clone();
-        let id2 = id.to_string();
-        thread::spawn(move || {
-            tx.send(
-                client2
-                    .0
-                    .channel(&id2, None)
-                    .map(|channel| Item::from_full_channel(channel, image_index).into_fullchannel())
-                    .ok(),
-            )
-        });
+        if attempt >= syncconfig.retries {
+            return Err(e);
+        }
+        thread::sleep(retry_delay(syncconfig.retry_delay_secs, attempt));
+        attempt += 1;
     }
-    let mut videos = client
-        .0
-        .channel_videos(id, None)?
-        .videos
-        .into_iter()
-        .map(|video| {
-            Item::from_channel_video(video, image_index)
-                .into_minivideo()
-                .unwrap()
-        })
-        .collect::<Vec<_>>();
-    videos.sort();
+}
 
+/// requests are made one after another in the calling thread, so each sync worker only has one
+/// request running at a time
+fn sync_one(
+    id: &str,
+    instance: &str,
+    image_index: usize,
+    download_thumbnails: bool,
+    sync_channel_info: bool,
+    feed_url: Option<&str>,
+    timeout: Duration,
+) -> Result<(Vec<MiniVideoItem>, Option<FullChannelItem>), Box<dyn Error>> {
     let channel = if sync_channel_info {
-        Some(
-            rx.recv()
-                .unwrap()
-                .ok_or(Errors::StrError("failed to get channel info {id}"))??,
-        )
+        let body = httpreq_get_timeout(&format!("{instance}/api/v1/channels/{id}"), timeout)?;
+        let channel: Channel = serde_json::from_slice(&body)?;
+        Some(Item::from_full_channel(channel, image_index).into_fullchannel()?)
     } else {
         None
     };
 
+    // falls back to invidious if the feed cannot be fetched
+    let videos = feed_or_else(feed_url, id, timeout, || {
+        let body =
+            httpreq_get_timeout(&format!("{instance}/api/v1/channels/{id}/videos"), timeout)?;
+        let mut videos = serde_json::from_slice::<ChannelVideos>(&body)?
+            .videos
+            .into_iter()
+            .map(|video| {
+                Item::from_channel_video(video, image_index)
+                    .into_minivideo()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        videos.sort();
+        Ok(videos)
+    })?;
+
     if download_thumbnails {
         let thumbnails: Vec<Option<DownloadRequest>> = videos
             .iter()
@@ -258,6 +383,9 @@ pub struct SubItem {
     pub last_sync: u64,
     pub last_sync_channel: u64,
     pub has_new: bool,
+    /// groups the channel is tagged with
+    #[serde(default)]
+    pub groups: Vec<String>,
 }
 
 impl Eq for SubItem {}
@@ -289,6 +417,127 @@ impl PartialOrd for SubItem {
     }
 }
 
+impl SubItem {
+    /// sync videos of the channel, and channel info if it is due
+    fn sync(
+        &mut self,
+        instances: &[String],
+        image_index: usize,
+        download_thumbnails: bool,
+        syncconfig: &SyncConfig,
+        filters: &FeedFilters,
+        now: u64,
+    ) -> SyncOutcome {
+        match sync_retrying(
+            &self.channel.id,
+            instances,
+            image_index,
+            download_thumbnails,
+            syncconfig.sync_channel_info
+                && syncconfig.sync_channel_cooldown_secs + self.last_sync_channel < now,
+            syncconfig,
+        ) {
+            Ok((videos, _channel)) if videos.is_empty() => SyncOutcome::Empty,
+            Ok((videos, channel)) => {
+                SyncOutcome::Success(self.update(filters.apply(videos), channel, now))
+            }
+            Err(_) => SyncOutcome::Failed,
+        }
+    }
+
+    /// returns videos that are not in the previous sync, nothing is new if the channel has never
+    /// been synced
+    fn update(
+        &mut self,
+        videos: Vec<MiniVideoItem>,
+        channel: Option<FullChannelItem>,
+        now: u64,
+    ) -> Vec<MiniVideoItem> {
+        // cannot just compare video publish timestamp to sync timestamp
+        // because publish timestamp is hugely inaccurate seen here
+        // https://github.com/iv-org/invidious/issues/570
+        self.has_new =
+            !videos.is_empty() && (self.videos.is_empty() || videos[0].id != self.videos[0].id);
+        let new_videos = if self.videos.is_empty() {
+            Vec::new()
+        } else {
+            videos
+                .iter()
+                .take_while(|video| self.videos.iter().all(|old| old.id != video.id))
+                .cloned()
+                .collect()
+        };
+        self.videos = videos;
+        self.last_sync = now;
+
+        if let Some(channel) = channel {
+            self.channel = channel;
+            self.last_sync_channel = now;
+        }
+
+        new_videos
+    }
+}
+
+/// a channel synced in the background by `Subscriptions::sync()`
+pub struct SyncedChannel {
+    pub item: SubItem,
+    pub outcome: SyncOutcome,
+}
+
+pub enum SyncOutcome {
+    /// with the new videos found
+    Success(Vec<MiniVideoItem>),
+    /// the channel has no videos
+    Empty,
+    Failed,
+}
+
+/// progress of syncing all subscriptions
+#[derive(Clone, Copy, Default)]
+pub struct SyncProgress {
+    /// number of channels to sync
+    pub total: u32,
+    pub done: u32,
+    pub success: u32,
+    pub failed: u32,
+    pub empty: u32,
+    /// channels skipped because they are synced recently
+    pub cached: u32,
+    pub new_videos: u32,
+}
+
+impl SyncProgress {
+    pub fn is_done(&self) -> bool {
+        self.done >= self.total
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "Subscriptions synced: {} success{} | {} fail | {} cached{}",
+            self.success,
+            if self.empty != 0 {
+                format!(" (which {} empty)", self.empty)
+            } else {
+                String::new()
+            },
+            self.failed,
+            self.cached,
+            if self.new_videos != 0 {
+                format!(" | {} new videos", self.new_videos)
+            } else {
+                String::new()
+            }
+        )
+    }
+}
+
+impl Display for SyncProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Synced {}/{}", self.done, self.total)
+    }
+}
+
 impl CollectionItem for SubItem {
     fn id(&self) -> Option<&str> {
         Some(&self.channel.id)
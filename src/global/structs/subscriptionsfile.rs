@@ -0,0 +1,320 @@
+use super::{FullChannelItem, SubItem, Subscriptions};
+use crate::global::functions::{expand_path, xml_attribute, xml_escape};
+use serde_json::{json, Value};
+use std::{error::Error, fmt::Display, fs};
+
+/// file formats that subscriptions can be imported from and exported to
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SubscriptionsFormat {
+    /// used by RSS readers and NewPipe
+    Opml,
+    /// `subscriptions.csv` from Google Takeout
+    TakeoutCsv,
+    NewPipeJson,
+    InvidiousJson,
+}
+
+/// a channel read from a subscriptions file
+pub struct ImportedChannel {
+    pub id: String,
+    pub name: String,
+}
+
+/// result of importing a subscriptions file
+pub struct ImportSummary {
+    pub format: SubscriptionsFormat,
+    pub added: usize,
+    /// channels that are already subscribed, or appears more than once in the file
+    pub duplicates: usize,
+}
+
+impl Display for SubscriptionsFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Opml => "OPML",
+            Self::TakeoutCsv => "Google Takeout CSV",
+            Self::NewPipeJson => "NewPipe JSON",
+            Self::InvidiousJson => "Invidious JSON",
+        })
+    }
+}
+
+impl Display for ImportSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Imported {} channel{} from {} ({} already subscribed), run `syncall` to fetch their videos",
+            self.added,
+            if self.added == 1 { "" } else { "s" },
+            self.format,
+            self.duplicates
+        )
+    }
+}
+
+impl SubscriptionsFormat {
+    /// format names used in `subscriptions export`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "opml" => Some(Self::Opml),
+            "csv" | "takeout" => Some(Self::TakeoutCsv),
+            "newpipe" => Some(Self::NewPipeJson),
+            "invidious" => Some(Self::InvidiousJson),
+            _ => None,
+        }
+    }
+
+    /// guess the format from the content of a file
+    pub fn detect(content: &str) -> Option<Self> {
+        let trimmed = content.trim_start_matches('\u{feff}').trim_start();
+        if trimmed.starts_with('<') {
+            return Some(Self::Opml);
+        }
+
+        if trimmed.starts_with('{') {
+            let value: Value = serde_json::from_str(trimmed).ok()?;
+            return match value.get("subscriptions")?.as_array()?.first() {
+                Some(Value::Object(_)) => Some(Self::NewPipeJson),
+                // an empty list can be either, but there is nothing to import anyways
+                Some(Value::String(_)) | None => Some(Self::InvidiousJson),
+                _ => None,
+            };
+        }
+
+        // the header is translated to the language of the google account, so the csv is recognised
+        // by its 3 columns with a channel id in the first
+        let mut lines = trimmed.lines().filter(|line| !line.trim().is_empty());
+        if csv_fields(lines.next()?).len() != 3 {
+            return None;
+        }
+        match lines.next() {
+            Some(line) => channel_id(csv_fields(line).first()?).map(|_| Self::TakeoutCsv),
+            // an empty takeout has nothing to import anyways
+            None => Some(Self::TakeoutCsv),
+        }
+    }
+
+    /// read channels from a file of this format
+    pub fn parse(&self, content: &str) -> Result<Vec<ImportedChannel>, Box<dyn Error>> {
+        let content = content.trim_start_matches('\u{feff}');
+        Ok(match self {
+            Self::Opml => content
+                .split("<outline")
+                .skip(1)
+                .filter_map(|tag| {
+                    let tag = &tag[..tag.find('>')?];
+                    let id = channel_id(&xml_attribute(tag, "xmlUrl")?)?;
+                    let name = xml_attribute(tag, "title")
+                        .or_else(|| xml_attribute(tag, "text"))
+                        .unwrap_or_else(|| id.clone());
+                    Some(ImportedChannel { id, name })
+                })
+                .collect(),
+            Self::TakeoutCsv => content
+                .lines()
+                .skip(1)
+                .filter_map(|line| {
+                    let fields = csv_fields(line);
+                    let id = channel_id(fields.first()?)?;
+                    let name = fields.get(2).cloned().unwrap_or_else(|| id.clone());
+                    Some(ImportedChannel { id, name })
+                })
+                .collect(),
+            Self::NewPipeJson => serde_json::from_str::<Value>(content)?
+                .get("subscriptions")
+                .and_then(Value::as_array)
+                .ok_or("No subscriptions found in file")?
+                .iter()
+                .filter_map(|channel| {
+                    let id = channel_id(channel.get("url")?.as_str()?)?;
+                    let name = channel
+                        .get("name")
+                        .and_then(Value::as_str)
+                        .map(str::to_string)
+                        .unwrap_or_else(|| id.clone());
+                    Some(ImportedChannel { id, name })
+                })
+                .collect(),
+            Self::InvidiousJson => serde_json::from_str::<Value>(content)?
+                .get("subscriptions")
+                .and_then(Value::as_array)
+                .ok_or("No subscriptions found in file")?
+                .iter()
+                .filter_map(|id| {
+                    let id = channel_id(id.as_str()?)?;
+                    Some(ImportedChannel {
+                        name: id.clone(),
+                        id,
+                    })
+                })
+                .collect(),
+        })
+    }
+
+    /// write channels in this format
+    pub fn write(&self, channels: &[&FullChannelItem]) -> Result<String, Box<dyn Error>> {
+        Ok(match self {
+            Self::Opml => {
+                let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<opml version=\"1.1\">\n<body>\n<outline text=\"YouTube Subscriptions\" title=\"YouTube Subscriptions\">\n");
+                channels.iter().for_each(|channel| {
+                    let name = xml_escape(&channel.name);
+                    out.push_str(&format!(
+                        "<outline text=\"{name}\" title=\"{name}\" type=\"rss\" xmlUrl=\"https://www.youtube.com/feeds/videos.xml?channel_id={}\"/>\n",
+                        channel.id
+                    ));
+                });
+                out.push_str("</outline>\n</body>\n</opml>\n");
+                out
+            }
+            Self::TakeoutCsv => {
+                let mut out = String::from("Channel Id,Channel Url,Channel Title\n");
+                channels.iter().for_each(|channel| {
+                    out.push_str(&format!(
+                        "{},http://www.youtube.com/channel/{},{}\n",
+                        channel.id,
+                        channel.id,
+                        csv_escape(&channel.name)
+                    ));
+                });
+                out
+            }
+            Self::NewPipeJson => serde_json::to_string_pretty(&json!({
+                "app_version": "0.25.2",
+                "app_version_int": 996,
+                "subscriptions": channels
+                    .iter()
+                    .map(|channel| json!({
+                        "service_id": 0,
+                        "url": format!("https://www.youtube.com/channel/{}", channel.id),
+                        "name": channel.name,
+                    }))
+                    .collect::<Vec<_>>(),
+            }))?,
+            Self::InvidiousJson => serde_json::to_string_pretty(&json!({
+                "subscriptions": channels
+                    .iter()
+                    .map(|channel| channel.id.as_str())
+                    .collect::<Vec<_>>(),
+            }))?,
+        })
+    }
+}
+
+impl Subscriptions {
+    /// add channels from a subscriptions file, the format is detected from the content
+    // imported channels have no videos and are synced on the next `syncall`, so importing does
+    // not need a network connection
+    pub fn import(&mut self, path: &str) -> Result<ImportSummary, Box<dyn Error>> {
+        let content = fs::read_to_string(expand_path(path))?;
+        let format = SubscriptionsFormat::detect(&content).ok_or("Unknown file format")?;
+        let channels = format.parse(&content)?;
+
+        let mut summary = ImportSummary {
+            format,
+            added: 0,
+            duplicates: 0,
+        };
+        for channel in channels {
+            if self.0.iter().any(|item| item.channel.id == channel.id) {
+                summary.duplicates += 1;
+                continue;
+            }
+
+            self.0.push(SubItem {
+                channel: FullChannelItem {
+                    name: channel.name,
+                    id: channel.id,
+                    thumbnail_url: String::new(),
+                    sub_count: 0,
+                    sub_count_text: String::new(),
+                    total_views: String::new(),
+                    created: String::new(),
+                    autogenerated: false,
+                    description: String::new(),
+                },
+                videos: Vec::new(),
+                last_sync: 0,
+                last_sync_channel: 0,
+                has_new: false,
+                groups: Vec::new(),
+            });
+            summary.added += 1;
+        }
+
+        self.0.sort();
+        Ok(summary)
+    }
+
+    /// write all subscribed channels to a file, returns the number of channels written
+    pub fn export(&self, path: &str, format: SubscriptionsFormat) -> Result<usize, Box<dyn Error>> {
+        let channels = self.0.iter().map(|item| &item.channel).collect::<Vec<_>>();
+        fs::write(expand_path(path), format.write(&channels)?)?;
+        Ok(channels.len())
+    }
+}
+
+/// get the channel id from an id, a channel url or a feed url
+fn channel_id(s: &str) -> Option<String> {
+    let s = s.trim();
+    let id = if let Some((_, id)) = s.split_once("channel_id=") {
+        id
+    } else if let Some((_, id)) = s.split_once("/channel/") {
+        id
+    } else {
+        s
+    };
+
+    let id = id.get(..24)?;
+    (id.starts_with("UC")
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+    .then(|| id.to_string())
+}
+
+/// split a csv line, fields can be quoted with `"` and quotes are escaped as `""`
+fn csv_fields(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            _ => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
+
+fn csv_escape(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_localized_takeout_csv() {
+        let csv = "ID de la chaîne,URL de la chaîne,Titre de la chaîne\n\
+            UC0123456789abcdefghijkl,http://www.youtube.com/channel/UC0123456789abcdefghijkl,\"A, B\"\n";
+        let format = SubscriptionsFormat::detect(csv);
+        assert!(format == Some(SubscriptionsFormat::TakeoutCsv));
+
+        let channels = format.unwrap().parse(csv).unwrap();
+        assert_eq!(channels.len(), 1);
+        assert_eq!(channels[0].id, "UC0123456789abcdefghijkl");
+        assert_eq!(channels[0].name, "A, B");
+
+        assert!(SubscriptionsFormat::detect("a,b,c\nnot a channel,x,y\n").is_none());
+    }
+}
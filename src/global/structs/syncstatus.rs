@@ -0,0 +1,46 @@
+use super::{SyncProgress, SyncedChannel};
+use std::{
+    sync::{mpsc, Arc, Mutex},
+    time::Instant,
+};
+use typemap::Key;
+
+/// state of syncing all subscriptions in the background
+#[derive(Clone)]
+pub struct SyncStatus {
+    sender: mpsc::Sender<SyncedChannel>,
+    /// synced channels, should be handled by the main thread with `sync_event()`
+    pub events: Arc<Mutex<mpsc::Receiver<SyncedChannel>>>,
+    /// `Some` while syncing
+    pub progress: Option<SyncProgress>,
+    /// progress of the current sync is not shown in the message bar
+    pub quiet: bool,
+    /// when the next scheduled sync should start
+    pub next_auto_sync: Option<Instant>,
+    /// new videos found since the feed page is last opened
+    pub new_videos: u32,
+}
+
+impl Key for SyncStatus {
+    type Value = Self;
+}
+
+impl Default for SyncStatus {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            sender,
+            events: Arc::new(Mutex::new(receiver)),
+            progress: None,
+            quiet: false,
+            next_auto_sync: None,
+            new_videos: 0,
+        }
+    }
+}
+
+impl SyncStatus {
+    pub fn sender(&self) -> mpsc::Sender<SyncedChannel> {
+        self.sender.clone()
+    }
+}
@@ -131,12 +131,12 @@ impl TaskQueue {
             Self::render_force_clear(framework, terminal)?;
             *framework.data.global.get_mut::<Message>().unwrap() = Message::None;
 
-            *framework.data.global.get_mut::<Message>().unwrap() = if let Err(e) = framework.load()
-            {
-                Message::Error(e.to_string())
-            } else {
-                Message::None
-            };
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                match load_with_failover(framework) {
+                    Ok(Some(message)) => Message::Message(message),
+                    Ok(None) => Message::None,
+                    Err(e) => Message::Error(e.to_string()),
+                };
             let status = framework.data.global.get_mut::<Status>().unwrap();
             status.provider_updated = true;
             status.render_image = true;
@@ -160,15 +160,18 @@ impl TaskQueue {
             );
 
             // reload simply runs `.load()` on all items
-            *framework.data.global.get_mut::<Message>().unwrap() =
-                Message::Message(String::from("Reloading page..."));
+            // the message of the command that asked for the reload is shown again once reloaded
+            let message = mem::replace(
+                framework.data.global.get_mut::<Message>().unwrap(),
+                Message::Message(String::from("Reloading page...")),
+            );
             Self::render_force_clear(framework, terminal)?;
-            *framework.data.global.get_mut::<Message>().unwrap() = if let Err(e) = framework.load()
-            {
-                Message::Error(e.to_string())
-            } else {
-                Message::None
-            };
+            *framework.data.global.get_mut::<Message>().unwrap() =
+                match load_with_failover(framework) {
+                    Ok(Some(message)) => Message::Message(message),
+                    Ok(None) => message,
+                    Err(e) => Message::Error(e.to_string()),
+                };
             let status = framework.data.global.get_mut::<Status>().unwrap();
             status.provider_updated = true;
             status.render_image = true;
@@ -78,4 +78,17 @@ pub trait ConfigTrait {
 
         Ok(Box::new(config))
     }
+
+    /// Overwrites the config file, comments in the old config file are removed
+    fn save(&self) -> Result<(), Box<dyn Error>>
+    where
+        Self: Serialize,
+    {
+        let config_path =
+            home_dir()
+                .unwrap()
+                .join(format!(".config/youtube-tui/{}.{}", Self::LABEL, EXTENSION));
+        fs::write(config_path, serde_yaml::to_string(self)?)?;
+        Ok(())
+    }
 }
@@ -27,6 +27,8 @@ pub fn init(
     [
         ".cache/youtube-tui/thumbnails/",
         ".cache/youtube-tui/info/",
+        ".cache/youtube-tui/comments/",
+        ".cache/youtube-tui/responses/",
         ".local/share/youtube-tui/thumbnails/",
         ".local/share/youtube-tui/info/",
         ".local/share/youtube-tui/saved/",
@@ -60,6 +62,37 @@ pub fn init(
         .global
         .insert::<Subscriptions>(Subscriptions::load());
     framework.data.global.insert::<Library>(Library::load());
+    framework
+        .data
+        .global
+        .insert::<OfflineIndex>(OfflineIndex::load());
+    framework.data.global.insert::<Queue>(Queue::load());
+    framework
+        .data
+        .global
+        .insert::<LocalPlaylists>(LocalPlaylists::load());
+    framework.data.global.insert::<Positions>(Positions::load());
+    // expired responses are not pruned, as they are still used in offline mode
+    framework
+        .data
+        .global
+        .insert::<ResponseCache>(ResponseCache::load());
+    framework
+        .data
+        .global
+        .insert::<SyncStatus>(SyncStatus::default());
+    framework
+        .data
+        .global
+        .insert::<SearchSuggestions>(SearchSuggestions::default());
+    framework
+        .data
+        .global
+        .insert::<Captions>(Captions::default());
+    let mut downloads = Downloads::load();
+    // continue downloads that were queued but not started last time
+    downloads.start_queued(framework.data.global.get::<MainConfig>().unwrap());
+    framework.data.global.insert::<Downloads>(downloads);
     framework.data.global.insert::<Message>(Message::None);
 
     framework.data.global.insert::<Status>(Status {
@@ -73,6 +106,26 @@ pub fn init(
         .global
         .insert::<MpvWrapper>(MpvWrapper::spawn());
 
+    // restore the play queue from last session into mpv, without playing anything
+    #[cfg(feature = "mpv")]
+    {
+        let queue = framework.data.global.get::<Queue>().unwrap();
+        if !queue.0.is_empty() {
+            queue.load_into(
+                framework.data.global.get::<MpvWrapper>().unwrap(),
+                0,
+                framework.data.global.get::<Status>().unwrap().provider,
+                &framework
+                    .data
+                    .global
+                    .get::<MainConfig>()
+                    .unwrap()
+                    .invidious_instance,
+                framework.data.global.get::<MainConfig>().unwrap(),
+            );
+        }
+    }
+
     framework.data.state.insert::<Tasks>(Tasks::default());
     framework.data.state.insert::<Page>(Page::default());
     framework
@@ -116,6 +169,10 @@ pub fn load_configs(framework: &mut FrameworkClean) -> Result<(), Box<dyn Error>
         .data
         .global
         .insert::<InvidiousClient>(InvidiousClient::new(main_config.invidious_instance.clone()));
+    framework
+        .data
+        .global
+        .insert::<Instances>(Instances::new(&main_config));
     framework
         .data
         .global
@@ -147,6 +204,12 @@ pub fn load_configs(framework: &mut FrameworkClean) -> Result<(), Box<dyn Error>
             .into()
             .unwrap(),
     );
+    framework
+        .data
+        .global
+        .insert::<FeedFilters>(FeedFilters::try_from(*FeedFiltersConfig::load(
+            main_config.write_config,
+        )?)?);
     framework
         .data
         .state
@@ -404,19 +404,18 @@ impl FrameworkItem for ChannelDisplay {
         let mainconfig = framework.data.global.get::<MainConfig>().unwrap();
         let appearance = framework.data.global.get::<AppearanceConfig>().unwrap();
         let page = framework.data.state.get::<Page>().unwrap().channeldisplay();
+        let client = &framework.data.global.get::<InvidiousClient>().unwrap().0;
+        let cache = framework.data.global.get::<ResponseCache>().unwrap();
 
         match page.r#type {
             ChannelDisplayPageType::Main => {
-                let channel = Item::from_full_channel(
-                    framework
-                        .data
-                        .global
-                        .get::<InvidiousClient>()
-                        .unwrap()
-                        .0
-                        .channel(&page.id, None)?,
-                    mainconfig.image_index,
-                );
+                let channel =
+                    cache.fetch(CacheKind::Channel, &page.id, &mainconfig.cache, || {
+                        Ok(Item::from_full_channel(
+                            client.channel(&page.id, None)?,
+                            mainconfig.image_index,
+                        ))
+                    })?;
                 if mainconfig.images.display() {
                     download_all_images(vec![(&channel).into()]);
                 }
@@ -426,17 +425,19 @@ impl FrameworkItem for ChannelDisplay {
                 }
             }
             ChannelDisplayPageType::Videos => {
-                let videos = framework
-                    .data
-                    .global
-                    .get::<InvidiousClient>()
-                    .unwrap()
-                    .0
-                    .channel_videos(&page.id, None)?
-                    .videos
-                    .into_iter()
-                    .map(|video| Item::from_channel_video(video, mainconfig.image_index))
-                    .collect::<Vec<_>>();
+                let videos: Vec<Item> = cache.fetch(
+                    CacheKind::Channel,
+                    &format!("{}/videos", page.id),
+                    &mainconfig.cache,
+                    || {
+                        Ok(client
+                            .channel_videos(&page.id, None)?
+                            .videos
+                            .into_iter()
+                            .map(|video| Item::from_channel_video(video, mainconfig.image_index))
+                            .collect())
+                    },
+                )?;
                 if mainconfig.images.display() {
                     download_all_images(videos.iter().map(|item| item.into()).collect());
                 }
@@ -456,17 +457,19 @@ impl FrameworkItem for ChannelDisplay {
                 };
             }
             ChannelDisplayPageType::Playlists => {
-                let playlists = framework
-                    .data
-                    .global
-                    .get::<InvidiousClient>()
-                    .unwrap()
-                    .0
-                    .channel_playlists(&page.id, None)?
-                    .playlists
-                    .into_iter()
-                    .map(Item::from_channel_playlist)
-                    .collect::<Vec<_>>();
+                let playlists: Vec<Item> = cache.fetch(
+                    CacheKind::Channel,
+                    &format!("{}/playlists", page.id),
+                    &mainconfig.cache,
+                    || {
+                        Ok(client
+                            .channel_playlists(&page.id, None)?
+                            .playlists
+                            .into_iter()
+                            .map(Item::from_channel_playlist)
+                            .collect())
+                    },
+                )?;
                 if mainconfig.images.display() {
                     download_all_images(playlists.iter().map(|item| item.into()).collect());
                 }
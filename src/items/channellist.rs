@@ -22,6 +22,8 @@ pub struct ChannelList {
     pub channel_display: ItemInfo,
     pub grid: Grid,
     pub channels: Vec<FullChannelItem>,
+    /// the group of channels being shown, all channels if `None`
+    pub group: Option<String>,
 }
 
 impl Default for ChannelList {
@@ -35,6 +37,7 @@ impl Default for ChannelList {
             )
             .unwrap(),
             channels: Vec::new(),
+            group: None,
         }
     }
 }
@@ -64,6 +67,39 @@ impl ChannelList {
     }
 
     fn select_at_cursor(&mut self, framework: &mut FrameworkClean) {
+        // selecting the first row switches to the next group
+        if self.selector.selected == 0 {
+            let subscriptions = framework.data.global.get::<Subscriptions>().unwrap();
+            let groups = subscriptions.groups();
+            if groups.is_empty() {
+                return;
+            }
+
+            let next = match &self.group {
+                Some(group) => groups
+                    .iter()
+                    .position(|name| name == group)
+                    .and_then(|index| groups.get(index + 1)),
+                None => groups.first(),
+            }
+            .map(|group| group.to_string());
+            framework
+                .data
+                .global
+                .get_mut::<Status>()
+                .unwrap()
+                .storage
+                .insert::<SubSelect>(SubSelect(0));
+            framework
+                .data
+                .state
+                .get_mut::<Tasks>()
+                .unwrap()
+                .priority
+                .push(Task::LoadPage(Page::Feed(next)));
+            return;
+        }
+
         let tasks = framework.data.state.get_mut::<Tasks>().unwrap();
 
         if self.selector.selected >= self.selector.items.len() {
@@ -73,13 +109,16 @@ impl ChannelList {
     }
 
     fn update_unread(&mut self, subscriptions: &Subscriptions) {
+        let channels = subscriptions.in_group(self.group.as_deref());
         self.selector
             .set_items(
                 &[format!(
-                    "All subscriptions{}",
-                    if !subscriptions.0.is_empty()
-                        && subscriptions.0.iter().any(|item| item.has_new)
-                    {
+                    "{}{}",
+                    match &self.group {
+                        Some(group) => format!("Group: {group}"),
+                        None => String::from("All subscriptions"),
+                    },
+                    if channels.iter().any(|item| item.has_new) {
                         "*"
                     } else {
                         ""
@@ -87,7 +126,7 @@ impl ChannelList {
                 )]
                 .into_iter()
                 .chain(
-                    subscriptions.0.iter().map(|subtiem| {
+                    channels.iter().map(|subtiem| {
                         format!("{subtiem}{}", if subtiem.has_new { "*" } else { "" })
                     }),
                 )
@@ -133,9 +172,20 @@ impl FrameworkItem for ChannelList {
         let subscriptions = framework.data.global.get::<Subscriptions>().unwrap();
         self.selector.set_border_type(appearance.borders);
         self.grid.set_border_type(appearance.borders);
-        self.channels = subscriptions.get_channels();
+        self.group = match framework.data.state.get::<Page>().unwrap() {
+            Page::Feed(group) => group.clone(),
+            _ => None,
+        };
+        self.channels = subscriptions.get_channels(self.group.as_deref());
 
         self.update_unread(subscriptions);
+        // the new videos badge on the feed button is cleared once the feed is seen
+        framework
+            .data
+            .global
+            .get_mut::<SyncStatus>()
+            .unwrap()
+            .new_videos = 0;
 
         if self.selector.selected >= self.selector.items.len() {
             self.selector.last()?;
@@ -164,7 +214,7 @@ impl FrameworkItem for ChannelList {
         self.selector.set_height(chunks[1].height);
         frame.render_widget(self.selector.clone(), chunks[1]);
 
-        if self.selector.items.len() == 1 {
+        if self.selector.items.len() == 1 && self.group.is_none() {
             frame.render_widget(
                 Paragraph::new("Subscribe to some channels first, come back later\n\nHey, Siriusmart here. I originally planned to add a commands textlist at channel main pages so that you can subscribe to channels, but the complexity of this update is starting to get out of hand, as it requires the two items (channel and video list) to communicate with each other somehow. So for now the only ways you can subscribe to channels in single item page (videos or playlists), or run `youtube-tui help` to check out the related commands.\n\nThe rest will come in a few git commits.").wrap(ratatui::widgets::Wrap { trim: true }),
                 chunks[0],
@@ -173,10 +223,35 @@ impl FrameworkItem for ChannelList {
         }
         if self.selector.selected == 0 {
             let now = chrono::Utc::now().timestamp() as u64;
-            let subscriptions = framework.data.global.get_mut::<Subscriptions>().unwrap();
+            let subscriptions = framework.data.global.get::<Subscriptions>().unwrap();
+            let groups = subscriptions.groups();
+            // the current group is shown in brackets
+            let label = |name: &str, current: bool| {
+                if current {
+                    format!("[{name}]")
+                } else {
+                    name.to_string()
+                }
+            };
+            let groups = if groups.is_empty() {
+                String::new()
+            } else {
+                format!(
+                    "Groups (select to switch): {}\n\n",
+                    [label("all", self.group.is_none())]
+                        .into_iter()
+                        .chain(
+                            groups
+                                .iter()
+                                .map(|group| label(group, self.group.as_deref() == Some(group))),
+                        )
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                )
+            };
             let paragraph = subscriptions
-                .0
-                .iter()
+                .in_group(self.group.as_deref())
+                .into_iter()
                 .map(|item| {
                     format!(
                         "  {} (last synced {} day{} ago){}",
@@ -193,7 +268,7 @@ impl FrameworkItem for ChannelList {
                 .collect::<Vec<_>>()
                 .join("\n");
             frame.render_widget(
-                Paragraph::new(format!("Subscriptions (last sync):\n\n{paragraph}")),
+                Paragraph::new(format!("{groups}Subscriptions (last sync):\n\n{paragraph}")),
                 chunks[0],
             );
             return;
@@ -284,23 +359,15 @@ impl FrameworkItem for ChannelList {
                     .get(self.selector.selected - 1)
                     .map(|channel| Item::FullChannel(channel.clone()));
                 let subscriptions = framework.data.global.get_mut::<Subscriptions>().unwrap();
-                let item = subscriptions.0.get_mut(self.selector.selected - 1);
+                // rows do not line up with subscriptions when a group is shown
                 let mut found = false;
-                match item {
-                    Some(item)
-                        if item.channel.id == self.channels[self.selector.selected - 1].id =>
-                    {
-                        if item.has_new {
-                            item.has_new = false;
-                            found = true;
-                        }
-                    }
-                    _ => subscriptions.0.iter_mut().for_each(|item| {
-                        if item.channel.id == self.channels[self.selector.selected - 1].id {
-                            found = true;
-                            item.has_new = false;
-                        }
-                    }),
+                if let Some(item) = subscriptions
+                    .0
+                    .iter_mut()
+                    .find(|item| item.channel.id == self.channels[self.selector.selected - 1].id)
+                {
+                    found = item.has_new;
+                    item.has_new = false;
                 }
 
                 if found {
@@ -323,16 +390,9 @@ impl FrameworkItem for ChannelList {
             return Ok(());
         }
 
-        match framework
-            .data
-            .global
-            .get::<Subscriptions>()
-            .unwrap()
-            .0
-            .get(self.selector.selected - 1)
-        {
+        match self.channels.get(self.selector.selected - 1) {
             Some(item)
-                if item.channel.id
+                if item.id
                     != match &self.channel_display.item {
                         Some(displayed) => displayed.id().unwrap_or_default(),
                         None => "",
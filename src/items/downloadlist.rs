@@ -0,0 +1,172 @@
+use crate::{
+    config::*,
+    global::{functions::*, structs::*},
+};
+use ratatui::{layout::Rect, style::Style};
+use std::error::Error;
+use tui_additions::{framework::FrameworkItem, widgets::TextList};
+
+/// list of download jobs and their progress
+#[derive(Clone, Default)]
+pub struct DownloadList {
+    pub textlist: TextList,
+    /// job numbers of the displayed jobs
+    pub jobs: Vec<u32>,
+}
+
+impl DownloadList {
+    /// update the displayed text to match the current download progress
+    fn update(&mut self, downloads: &Downloads) {
+        self.jobs = downloads.jobs.iter().map(|job| job.job).collect();
+        let lines = if downloads.jobs.is_empty() {
+            vec![String::from("No downloads")]
+        } else {
+            downloads.jobs.iter().map(DownloadJob::to_string).collect()
+        };
+
+        // progress changes all the time, so only reset the list if the number of jobs changed
+        if self.textlist.items.len() == lines.len() {
+            self.textlist.items = lines;
+        } else {
+            let _ = self.textlist.set_items(&lines);
+        }
+    }
+
+    fn infalte_item_update(&self) -> Vec<(String, String)> {
+        match self.jobs.get(self.textlist.selected) {
+            Some(job) => vec![(String::from("hover-job"), job.to_string())],
+            None => Vec::new(),
+        }
+    }
+
+    /// view the item being downloaded
+    fn select_at_cursor(&self, framework: &mut tui_additions::framework::FrameworkClean) {
+        let job = match self.jobs.get(self.textlist.selected).and_then(|job| {
+            framework
+                .data
+                .global
+                .get::<Downloads>()
+                .unwrap()
+                .jobs
+                .iter()
+                .find(|item| item.job == *job)
+        }) {
+            Some(job) => job,
+            None => return,
+        };
+
+        let command = format!(
+            "{} {}",
+            if job.is_playlist { "playlist" } else { "video" },
+            job.id
+        );
+        framework
+            .data
+            .state
+            .get_mut::<Tasks>()
+            .unwrap()
+            .priority
+            .push(Task::Command(command));
+    }
+}
+
+impl FrameworkItem for DownloadList {
+    fn render(
+        &mut self,
+        frame: &mut ratatui::Frame<ratatui::backend::CrosstermBackend<std::io::Stdout>>,
+        framework: &mut tui_additions::framework::FrameworkClean,
+        area: Rect,
+        popup_render: bool,
+        info: tui_additions::framework::ItemInfo,
+    ) {
+        if popup_render {
+            return;
+        }
+
+        self.update(framework.data.global.get::<Downloads>().unwrap());
+
+        let appearance = framework.data.global.get::<AppearanceConfig>().unwrap();
+        self.textlist.set_border_type(appearance.borders);
+        self.textlist
+            .set_style(Style::default().fg(appearance.colors.text));
+        if info.selected {
+            self.textlist
+                .set_cursor_style(Style::default().fg(appearance.colors.outline_hover));
+            self.textlist
+                .set_selected_style(Style::default().fg(appearance.colors.text_special));
+        } else {
+            self.textlist
+                .set_cursor_style(Style::default().fg(appearance.colors.outline_secondary));
+            self.textlist
+                .set_selected_style(Style::default().fg(appearance.colors.text_secondary));
+        }
+
+        self.textlist.set_height(area.height);
+        frame.render_widget(self.textlist.clone(), area);
+    }
+
+    fn load_item(
+        &mut self,
+        framework: &mut tui_additions::framework::FrameworkClean,
+        _info: tui_additions::framework::ItemInfo,
+    ) -> Result<(), Box<dyn Error>> {
+        *self = Self::default();
+        self.update(framework.data.global.get::<Downloads>().unwrap());
+        set_envs(
+            self.infalte_item_update().into_iter(),
+            &mut framework.data.state.get_mut::<StateEnvs>().unwrap().0,
+        );
+        Ok(())
+    }
+
+    fn selectable(&self) -> bool {
+        true
+    }
+
+    fn key_event(
+        &mut self,
+        framework: &mut tui_additions::framework::FrameworkClean,
+        key: crossterm::event::KeyEvent,
+        _info: tui_additions::framework::ItemInfo,
+    ) -> Result<(), Box<dyn Error>> {
+        let action = if let Some(action) = framework
+            .data
+            .global
+            .get::<KeyBindingsConfig>()
+            .unwrap()
+            .get(key)
+        {
+            action
+        } else {
+            return Ok(());
+        };
+
+        let updated = match action {
+            KeyAction::MoveUp => self.textlist.up().is_ok(),
+            KeyAction::MoveDown => self.textlist.down().is_ok(),
+            KeyAction::MoveLeft | KeyAction::First => self.textlist.first().is_ok(),
+            KeyAction::MoveRight | KeyAction::End => self.textlist.last().is_ok(),
+            KeyAction::Select => {
+                self.select_at_cursor(framework);
+                false
+            }
+            _ => false,
+        };
+
+        if updated {
+            set_envs(
+                self.infalte_item_update().into_iter(),
+                &mut framework.data.state.get_mut::<StateEnvs>().unwrap().0,
+            );
+            framework
+                .data
+                .state
+                .get_mut::<Tasks>()
+                .unwrap()
+                .priority
+                .push(Task::RenderAll);
+        }
+
+        Ok(())
+    }
+}
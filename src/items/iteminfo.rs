@@ -103,7 +103,7 @@ impl FrameworkItem for ItemInfo {
 
         // Each "span" contains a string and a Style, and they are one line max each
         // A "text" is used for descriptions in video/playlist and channels, and starts a new line if the old one runs out
-        let (spans, text) = match item {
+        let (mut spans, text) = match item {
             Item::MiniVideo(minivideo) => {
                 let mut out = (
                     vec![
@@ -337,6 +337,28 @@ impl FrameworkItem for ItemInfo {
             ),
         };
 
+        // shows how much of a video in watch history is played through the embedded player
+        if let Item::MiniVideo(MiniVideoItem { id, .. })
+        | Item::FullVideo(FullVideoItem { id, .. }) = item
+        {
+            if let Some(position) = framework.data.global.get::<Positions>().unwrap().get(id) {
+                if framework
+                    .data
+                    .global
+                    .get::<WatchHistory>()
+                    .unwrap()
+                    .0
+                    .iter()
+                    .any(|item| item.id() == Some(id))
+                {
+                    spans.push((
+                        format!("Watched {}%", position.percentage()),
+                        Style::default().fg(appearance.colors.item_info.length),
+                    ));
+                }
+            }
+        }
+
         let mut y = if scroll >= area.height { 0 } else { scroll } + area.y;
         let bottom = area.bottom();
 
@@ -1,4 +1,4 @@
-use std::error::Error;
+use std::{collections::HashSet, error::Error};
 
 use crate::{
     config::*,
@@ -23,6 +23,10 @@ pub struct ItemList {
     pub items: Vec<Item>,
     pub textlist: TextList,
     pub grid: Grid,
+    /// index of the currently playing item, only used in the queue page
+    pub playing: Option<usize>,
+    /// saved file info of each item in the library page, or unwatched videos in the timeline
+    pub badges: Vec<Option<String>>,
 }
 
 impl ItemList {
@@ -35,7 +39,7 @@ impl ItemList {
             return Vec::new();
         }
 
-        match &self.items[self.textlist.selected] {
+        let mut envs = match &self.items[self.textlist.selected] {
             Item::MiniVideo(MiniVideoItem { id, .. })
             | Item::FullVideo(FullVideoItem { id, .. }) => {
                 vec![(
@@ -78,7 +82,34 @@ impl ItemList {
             Item::Page(_) | Item::Unknown(_) => {
                 vec![(String::from("hover-url"), String::from("not avaliable"))]
             }
-        }
+        };
+
+        envs.push((
+            String::from("hover-id"),
+            self.items[self.textlist.selected]
+                .id()
+                .unwrap_or("not avaliable")
+                .to_string(),
+        ));
+        envs
+    }
+
+    /// labels of the items, with the currently playing item in queue and saved items in library
+    /// marked
+    fn labels(&self) -> Vec<String> {
+        self.items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                if Some(index) == self.playing {
+                    format!("[Playing] {item}")
+                } else if let Some(Some(badge)) = self.badges.get(index) {
+                    format!("[{badge}] {item}")
+                } else {
+                    item.to_string()
+                }
+            })
+            .collect()
     }
 
     fn update_appearance(
@@ -153,7 +184,10 @@ impl ItemList {
                         page: if *b { search.page + 1 } else { search.page - 1 },
                         ..search.clone()
                     })),
-                    _ => unreachable!("Page turners can only be used in search pages"),
+                    Page::Timeline(page) => {
+                        Some(Page::Timeline(if *b { page + 1 } else { page - 1 }))
+                    }
+                    _ => unreachable!("Page turners can only be used in search and timeline pages"),
                 },
             }
         } else {
@@ -190,7 +224,10 @@ impl ItemList {
                         page: if *b { search.page + 1 } else { search.page - 1 },
                         ..search.clone()
                     })),
-                    _ => unreachable!("Page turners can only be used in search pages"),
+                    Page::Timeline(page) => {
+                        Some(Page::Timeline(if *b { page + 1 } else { page - 1 }))
+                    }
+                    _ => unreachable!("Page turners can only be used in search and timeline pages"),
                 },
             }
         };
@@ -218,6 +255,8 @@ impl Default for ItemList {
                 vec![Constraint::Percentage(100)],
             )
             .unwrap(),
+            playing: None,
+            badges: Vec::new(),
         }
     }
 }
@@ -248,6 +287,21 @@ impl FrameworkItem for ItemList {
 
         self.update_appearance(appearance, mainconfig, &info);
 
+        // the playing item changes without the page being reloaded, so check for it on every render
+        #[cfg(feature = "mpv")]
+        if framework.data.state.get::<Page>().unwrap() == &Page::Queue {
+            let playing = framework
+                .data
+                .global
+                .get::<Queue>()
+                .unwrap()
+                .playing(framework.data.global.get::<MpvWrapper>().unwrap());
+            if playing != self.playing {
+                self.playing = playing;
+                let _ = self.textlist.set_items(&self.labels());
+            }
+        }
+
         // creates the grid
         let grid = self.grid.clone();
         let chunks = grid.chunks(area).unwrap()[0].clone();
@@ -290,28 +344,51 @@ impl FrameworkItem for ItemList {
             .unwrap()
             .image_index;
         let client = &framework.data.global.get::<InvidiousClient>().unwrap().0;
+        let cache = framework.data.global.get::<ResponseCache>().unwrap();
+        let cacheconfig = &framework.data.global.get::<MainConfig>().unwrap().cache;
 
         // fetch the items using the invidious api
         match page {
             Page::MainMenu(MainMenuPage::Trending) => {
-                self.items = client
-                    .trending(None)?
-                    .videos
-                    .into_iter()
-                    .map(|item| Item::from_trending_video(item, image_index))
-                    .collect();
+                self.items = cache.fetch(CacheKind::Trending, "trending", cacheconfig, || {
+                    Ok(client
+                        .trending(None)?
+                        .videos
+                        .into_iter()
+                        .map(|item| Item::from_trending_video(item, image_index))
+                        .collect())
+                })?;
             }
             Page::MainMenu(MainMenuPage::Popular) => {
-                self.items = client
-                    .popular(None)?
-                    .items
-                    .into_iter()
-                    .map(|item| Item::from_popular_item(item, image_index))
-                    .collect();
+                self.items = cache.fetch(CacheKind::Trending, "popular", cacheconfig, || {
+                    Ok(client
+                        .popular(None)?
+                        .items
+                        .into_iter()
+                        .map(|item| Item::from_popular_item(item, image_index))
+                        .collect())
+                })?;
             }
             Page::MainMenu(MainMenuPage::Library) => {
-                let history = framework.data.global.get::<Library>().unwrap();
-                self.items = history.0.clone().into_iter().rev().collect();
+                // rescan saved files, as they may be changed outside of the TUI
+                let mut index = framework.data.global.remove::<OfflineIndex>().unwrap();
+                let library = framework.data.global.get::<Library>().unwrap();
+                self.items = library.0.clone().into_iter().rev().collect();
+
+                if let Some(save_path) =
+                    save_path(framework.data.global.get::<MainConfig>().unwrap())
+                {
+                    let downloads = framework.data.global.get::<Downloads>().unwrap();
+                    if index.scan(&save_path, library, downloads).is_ok() {
+                        let _ = index.save();
+                    }
+                }
+                self.badges = self
+                    .items
+                    .iter()
+                    .map(|item| Some(index.get(item.id()?)?.to_string()))
+                    .collect();
+                framework.data.global.insert::<OfflineIndex>(index);
             }
             Page::MainMenu(MainMenuPage::History) => {
                 // the vector needs to be reversed because the latest watch history is pushed to
@@ -319,13 +396,89 @@ impl FrameworkItem for ItemList {
                 let history = framework.data.global.get::<WatchHistory>().unwrap();
                 self.items = history.0.clone().into_iter().rev().collect();
             }
-            Page::Search(search) => {
-                self.items = client
-                    .search(Some(&search.to_string()))?
+            Page::Queue => {
+                // unlike history, the queue is displayed in the order it is played
+                self.items = framework.data.global.get::<Queue>().unwrap().0.clone();
+                #[cfg(feature = "mpv")]
+                {
+                    self.playing = framework
+                        .data
+                        .global
+                        .get::<Queue>()
+                        .unwrap()
+                        .playing(framework.data.global.get::<MpvWrapper>().unwrap());
+                }
+            }
+            Page::LocalPlaylist(name) => {
+                self.items = framework
+                    .data
+                    .global
+                    .get::<LocalPlaylists>()
+                    .unwrap()
+                    .get(name)
+                    .ok_or_else(|| format!("Playlist `{name}` does not exist"))?
                     .items
-                    .into_iter()
-                    .map(|item| Item::from_search_item(item, image_index))
+                    .clone();
+            }
+            Page::Timeline(page) => {
+                let page_size = framework
+                    .data
+                    .global
+                    .get::<MainConfig>()
+                    .unwrap()
+                    .limits
+                    .timeline_page
+                    .max(1);
+                // rules added after syncing also apply to videos already synced
+                let videos = framework.data.global.get::<FeedFilters>().unwrap().apply(
+                    framework
+                        .data
+                        .global
+                        .get::<Subscriptions>()
+                        .unwrap()
+                        .timeline(),
+                );
+                let watched = framework
+                    .data
+                    .global
+                    .get::<WatchHistory>()
+                    .unwrap()
+                    .0
+                    .iter()
+                    .filter_map(Item::id)
+                    .collect::<HashSet<_>>();
+
+                let start = (((*page).max(1) - 1) * page_size).min(videos.len());
+                let end = (start + page_size).min(videos.len());
+                self.badges = videos[start..end]
+                    .iter()
+                    .map(|video| {
+                        (!watched.contains(video.id.as_str())).then(|| String::from("Unwatched"))
+                    })
+                    .collect();
+                self.items = videos[start..end]
+                    .iter()
+                    .cloned()
+                    .map(Item::MiniVideo)
                     .collect();
+                if end < videos.len() {
+                    self.items.push(Item::Page(true));
+                }
+                if *page > 1 {
+                    self.items.insert(0, Item::Page(false));
+                    self.badges.insert(0, None);
+                }
+            }
+            Page::Search(search) => {
+                let query = search.to_string();
+                self.items = cache.fetch(CacheKind::Search, &query, cacheconfig, || {
+                    Ok(client
+                        .search(Some(&query))?
+                        .items
+                        .into_iter()
+                        .map(|item| Item::from_search_item(item, image_index))
+                        .collect())
+                })?;
                 if !self.items.is_empty() {
                     self.items.push(Item::Page(true));
                 }
@@ -337,7 +490,7 @@ impl FrameworkItem for ItemList {
         }
 
         // update the items in text list
-        self.textlist.set_items(&self.items).unwrap();
+        self.textlist.set_items(&self.labels()).unwrap();
         self.update(framework);
 
         let mainconfig = framework.data.global.get::<MainConfig>().unwrap();
@@ -0,0 +1,151 @@
+use crate::{
+    config::*,
+    global::{functions::*, structs::*},
+};
+use ratatui::{layout::Rect, style::Style};
+use std::error::Error;
+use tui_additions::{framework::FrameworkItem, widgets::TextList};
+
+/// list of local playlists
+#[derive(Clone, Default)]
+pub struct LocalPlaylistList {
+    pub textlist: TextList,
+    /// names of the displayed playlists
+    pub names: Vec<String>,
+}
+
+impl LocalPlaylistList {
+    fn infalte_item_update(&self) -> Vec<(String, String)> {
+        match self.names.get(self.textlist.selected) {
+            Some(name) => vec![(String::from("hover-playlist"), name.clone())],
+            None => Vec::new(),
+        }
+    }
+
+    /// view the items in the playlist
+    fn select_at_cursor(&self, framework: &mut tui_additions::framework::FrameworkClean) {
+        if let Some(name) = self.names.get(self.textlist.selected) {
+            framework
+                .data
+                .state
+                .get_mut::<Tasks>()
+                .unwrap()
+                .priority
+                .push(Task::LoadPage(Page::LocalPlaylist(name.clone())));
+        }
+    }
+}
+
+impl FrameworkItem for LocalPlaylistList {
+    fn render(
+        &mut self,
+        frame: &mut ratatui::Frame<ratatui::backend::CrosstermBackend<std::io::Stdout>>,
+        framework: &mut tui_additions::framework::FrameworkClean,
+        area: Rect,
+        popup_render: bool,
+        info: tui_additions::framework::ItemInfo,
+    ) {
+        if popup_render {
+            return;
+        }
+
+        let appearance = framework.data.global.get::<AppearanceConfig>().unwrap();
+        self.textlist.set_border_type(appearance.borders);
+        self.textlist
+            .set_style(Style::default().fg(appearance.colors.text));
+        if info.selected {
+            self.textlist
+                .set_cursor_style(Style::default().fg(appearance.colors.outline_hover));
+            self.textlist
+                .set_selected_style(Style::default().fg(appearance.colors.text_special));
+        } else {
+            self.textlist
+                .set_cursor_style(Style::default().fg(appearance.colors.outline_secondary));
+            self.textlist
+                .set_selected_style(Style::default().fg(appearance.colors.text_secondary));
+        }
+
+        self.textlist.set_height(area.height);
+        frame.render_widget(self.textlist.clone(), area);
+    }
+
+    fn load_item(
+        &mut self,
+        framework: &mut tui_additions::framework::FrameworkClean,
+        _info: tui_additions::framework::ItemInfo,
+    ) -> Result<(), Box<dyn Error>> {
+        *self = Self::default();
+
+        let playlists = framework.data.global.get::<LocalPlaylists>().unwrap();
+        self.names = playlists
+            .0
+            .iter()
+            .map(|playlist| playlist.name.clone())
+            .collect();
+        let lines = if playlists.0.is_empty() {
+            vec![String::from(
+                "No playlists, create one with `playlist create {name}`",
+            )]
+        } else {
+            playlists.0.iter().map(LocalPlaylist::to_string).collect()
+        };
+        self.textlist.set_items(&lines)?;
+
+        set_envs(
+            self.infalte_item_update().into_iter(),
+            &mut framework.data.state.get_mut::<StateEnvs>().unwrap().0,
+        );
+        Ok(())
+    }
+
+    fn selectable(&self) -> bool {
+        true
+    }
+
+    fn key_event(
+        &mut self,
+        framework: &mut tui_additions::framework::FrameworkClean,
+        key: crossterm::event::KeyEvent,
+        _info: tui_additions::framework::ItemInfo,
+    ) -> Result<(), Box<dyn Error>> {
+        let action = if let Some(action) = framework
+            .data
+            .global
+            .get::<KeyBindingsConfig>()
+            .unwrap()
+            .get(key)
+        {
+            action
+        } else {
+            return Ok(());
+        };
+
+        let updated = match action {
+            KeyAction::MoveUp => self.textlist.up().is_ok(),
+            KeyAction::MoveDown => self.textlist.down().is_ok(),
+            KeyAction::MoveLeft | KeyAction::First => self.textlist.first().is_ok(),
+            KeyAction::MoveRight | KeyAction::End => self.textlist.last().is_ok(),
+            KeyAction::Select => {
+                self.select_at_cursor(framework);
+                false
+            }
+            _ => false,
+        };
+
+        if updated {
+            set_envs(
+                self.infalte_item_update().into_iter(),
+                &mut framework.data.state.get_mut::<StateEnvs>().unwrap().0,
+            );
+            framework
+                .data
+                .state
+                .get_mut::<Tasks>()
+                .unwrap()
+                .priority
+                .push(Task::RenderAll);
+        }
+
+        Ok(())
+    }
+}
@@ -2,8 +2,9 @@
 use crate::global::functions::secs_display_string;
 use crate::{config::*, global::structs::*};
 use ratatui::{
-    layout::Rect,
+    layout::{Alignment, Rect},
     style::Style,
+    text::Span,
     widgets::{Block, Borders, Paragraph},
 };
 use tui_additions::framework::*;
@@ -108,7 +109,7 @@ impl FrameworkItem for MessageBar {
             .command_capture;
 
         // display with different border style according to type of message and config
-        let block = Block::default()
+        let mut block = Block::default()
             .borders(Borders::ALL)
             .border_type(appearance.borders)
             .border_style(Style::default().fg(if command_capture.is_some() {
@@ -122,6 +123,21 @@ impl FrameworkItem for MessageBar {
                 }
             }));
 
+        if framework
+            .data
+            .global
+            .get::<ResponseCache>()
+            .unwrap()
+            .offline
+        {
+            block = block
+                .title(Span::styled(
+                    " OFFLINE ",
+                    Style::default().fg(appearance.colors.message_error_outline),
+                ))
+                .title_alignment(Alignment::Right);
+        }
+
         // if keys are captured, render the textlist instead of the message text, and exits the
         // function
         if let Some(textfield) = command_capture {
@@ -19,3 +19,9 @@ mod channellist;
 pub use channellist::*;
 mod videolist;
 pub use videolist::*;
+mod nowplaying;
+pub use nowplaying::*;
+mod downloadlist;
+pub use downloadlist::*;
+mod localplaylistlist;
+pub use localplaylistlist::*;
@@ -0,0 +1,361 @@
+#[cfg(feature = "mpv")]
+use crate::global::functions::{download_all_images, secs_display_string};
+use crate::{config::*, global::structs::*};
+#[cfg(feature = "mpv")]
+use home::home_dir;
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    widgets::{Block, Borders, Paragraph},
+};
+#[cfg(feature = "mpv")]
+use std::fs;
+use tui_additions::framework::FrameworkItem;
+#[cfg(all(feature = "mpv", any(feature = "sixel", feature = "halfblock")))]
+use viuer::{print_from_file, Config};
+
+/// a full page view of the built in player, with controls and chapters of the currently playing
+/// video
+#[derive(Clone, Default)]
+pub struct NowPlaying {
+    /// info of the currently playing video, `None` if it is not a video loaded by the TUI
+    pub item: Option<Item>,
+    /// id of the currently playing video
+    pub id: Option<String>,
+    /// where the seek bar is in the last render, used for seeking with mouse clicks
+    pub seekbar: Option<Rect>,
+    pub lazy_scroll: u16,
+}
+
+impl FrameworkItem for NowPlaying {
+    fn render(
+        &mut self,
+        frame: &mut ratatui::Frame<ratatui::backend::CrosstermBackend<std::io::Stdout>>,
+        framework: &mut tui_additions::framework::FrameworkClean,
+        area: Rect,
+        popup_render: bool,
+        info: tui_additions::framework::ItemInfo,
+    ) {
+        if popup_render {
+            return;
+        }
+
+        let appearance = framework.data.global.get::<AppearanceConfig>().unwrap();
+
+        let block = Block::default()
+            .border_type(appearance.borders)
+            .border_style(Style::default().fg(if info.selected {
+                appearance.colors.outline_selected
+            } else if info.hover {
+                appearance.colors.outline_hover
+            } else {
+                appearance.colors.outline
+            }))
+            .borders(Borders::ALL)
+            .title("Now Playing");
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        #[cfg(not(feature = "mpv"))]
+        frame.render_widget(
+            Paragraph::new("Feature `mpv` is disabled and not compiled")
+                .style(Style::default().fg(appearance.colors.text_error)),
+            inner,
+        );
+
+        #[cfg(feature = "mpv")]
+        self.render_player(frame, framework, inner);
+    }
+
+    fn load_item(
+        &mut self,
+        _framework: &mut tui_additions::framework::FrameworkClean,
+        _info: tui_additions::framework::ItemInfo,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        *self = Self::default();
+        Ok(())
+    }
+
+    fn selectable(&self) -> bool {
+        true
+    }
+
+    // clicking on the seek bar seeks to that position
+    fn mouse_event(
+        &mut self,
+        framework: &mut tui_additions::framework::FrameworkClean,
+        _x: u16,
+        _y: u16,
+        absolute_x: u16,
+        absolute_y: u16,
+    ) -> bool {
+        #[cfg(feature = "mpv")]
+        if let Some(seekbar) = self.seekbar {
+            if seekbar.intersects(Rect::new(absolute_x, absolute_y, 1, 1)) {
+                let percentage =
+                    (absolute_x - seekbar.x) as f64 * 100.0 / (seekbar.width.max(2) - 1) as f64;
+                framework.data.global.get::<MpvWrapper>().unwrap().command(
+                    String::from("seek"),
+                    vec![percentage.to_string(), String::from("absolute-percent")],
+                );
+                return true;
+            }
+        }
+
+        #[cfg(not(feature = "mpv"))]
+        let _ = (framework, absolute_x, absolute_y);
+
+        false
+    }
+}
+
+#[cfg(feature = "mpv")]
+impl NowPlaying {
+    fn render_player(
+        &mut self,
+        frame: &mut ratatui::Frame<ratatui::backend::CrosstermBackend<std::io::Stdout>>,
+        framework: &mut tui_additions::framework::FrameworkClean,
+        area: Rect,
+    ) {
+        let path = framework
+            .data
+            .global
+            .get::<MpvWrapper>()
+            .unwrap()
+            .player
+            .path
+            .clone();
+        let path = if let Some(path) = path {
+            path
+        } else {
+            self.seekbar = None;
+            let appearance = framework.data.global.get::<AppearanceConfig>().unwrap();
+            frame.render_widget(
+                Paragraph::new("Nothing is playing")
+                    .style(Style::default().fg(appearance.colors.text_secondary)),
+                area,
+            );
+            return;
+        };
+
+        // a new video is loaded, get its info from cache and display the new thumbnail
+        let id = Positions::id_from_path(&path);
+        if id != self.id {
+            self.item = id.as_ref().and_then(|id| {
+                serde_json::from_str::<Item>(
+                    &fs::read_to_string(
+                        home_dir()
+                            .unwrap()
+                            .join(format!(".cache/youtube-tui/info/{id}.json")),
+                    )
+                    .ok()?,
+                )
+                .ok()
+            });
+            self.id = id;
+            self.lazy_scroll = 0;
+            if let Some(item) = &self.item {
+                if framework
+                    .data
+                    .global
+                    .get::<MainConfig>()
+                    .unwrap()
+                    .images
+                    .display()
+                {
+                    download_all_images(vec![item.into()]);
+                }
+            }
+            framework
+                .data
+                .global
+                .get_mut::<Status>()
+                .unwrap()
+                .render_image = true;
+        }
+
+        let scroll = self.render_thumbnail(framework, area);
+
+        // everything else is kept from mpv events, only the position and pause state are polled
+        let mpv = framework.data.global.get::<MpvWrapper>().unwrap();
+        let player = &mpv.player;
+        let appearance = framework.data.global.get::<AppearanceConfig>().unwrap();
+
+        let title = match &self.item {
+            Some(item) => item.to_string(),
+            None => player.media_title.clone(),
+        };
+        let channel = match &self.item {
+            Some(Item::MiniVideo(MiniVideoItem { channel, .. }))
+            | Some(Item::FullVideo(FullVideoItem { channel, .. })) => {
+                format!("Uploaded by {channel}")
+            }
+            _ => String::new(),
+        };
+
+        let time = mpv
+            .property(String::from("time-pos"))
+            .and_then(|time| time.parse::<f64>().ok())
+            .unwrap_or_default();
+        let duration = player.float_property("duration");
+        let time_s = secs_display_string(time as u32);
+        let duration_s = secs_display_string(duration as u32);
+
+        // the seek bar takes up the whole width, except the space for the timestamps
+        let seekbar_width = (area.width as usize)
+            .saturating_sub(time_s.len() + duration_s.len() + 2)
+            .max(3);
+        let seeker_pos = if duration > 0.0 {
+            ((seekbar_width - 1) as f64 * time / duration) as usize
+        } else {
+            0
+        }
+        .min(seekbar_width - 1);
+        let seekbar = format!(
+            "{time_s} {}●{} {duration_s}",
+            "━".repeat(seeker_pos),
+            "─".repeat(seekbar_width - seeker_pos - 1),
+        );
+
+        let looping = |name: &str| {
+            player
+                .properties
+                .get(name)
+                .is_some_and(|value| value != "no")
+        };
+        let loop_mode = if looping("loop-file") {
+            "file"
+        } else if looping("loop-playlist") {
+            "playlist"
+        } else {
+            "off"
+        };
+        let status = format!(
+            "{}  Volume: {}%  Speed: {:.2}x  Loop: {loop_mode}",
+            if mpv.property(String::from("pause")).as_deref() == Some("yes") {
+                "Paused"
+            } else {
+                "Playing"
+            },
+            player.float_property("volume") as u32,
+            player.float_property("speed"),
+        );
+
+        let mut lines = vec![
+            (
+                title,
+                Style::default().fg(appearance.colors.item_info.title),
+            ),
+            (
+                channel,
+                Style::default().fg(appearance.colors.item_info.author),
+            ),
+            (String::new(), Style::default()),
+            (seekbar, Style::default().fg(appearance.colors.text)),
+            (
+                status,
+                Style::default().fg(appearance.colors.item_info.length),
+            ),
+        ];
+
+        if !player.chapters.is_empty() {
+            let current = player
+                .chapters
+                .iter()
+                .rposition(|(start, _)| *start <= time);
+            lines.push((String::new(), Style::default()));
+            lines.push((
+                String::from("Chapters:"),
+                Style::default().fg(appearance.colors.item_info.tag),
+            ));
+            for (index, (time, title)) in player.chapters.iter().enumerate() {
+                lines.push(if current == Some(index) {
+                    (
+                        format!("> {} {title}", secs_display_string(*time as u32)),
+                        Style::default().fg(appearance.colors.text_special),
+                    )
+                } else {
+                    (
+                        format!("  {} {title}", secs_display_string(*time as u32)),
+                        Style::default().fg(appearance.colors.text),
+                    )
+                });
+            }
+        }
+
+        self.seekbar = None;
+        let top = if scroll >= area.height { 0 } else { scroll } + area.y;
+        for (index, ((text, style), y)) in lines.into_iter().zip(top..area.bottom()).enumerate() {
+            // 4th line is the seek bar
+            if index == 3 {
+                self.seekbar = Some(Rect::new(
+                    area.x + time_s.len() as u16 + 1,
+                    y,
+                    seekbar_width as u16,
+                    1,
+                ));
+            }
+
+            frame.render_widget(
+                Paragraph::new(text).style(style),
+                Rect {
+                    y,
+                    height: 1,
+                    ..area
+                },
+            );
+        }
+    }
+
+    /// displays the thumbnail of the playing video, returns its height
+    // works the same as in `ItemInfo`
+    fn render_thumbnail(
+        &mut self,
+        framework: &mut tui_additions::framework::FrameworkClean,
+        area: Rect,
+    ) -> u16 {
+        let main_config = framework.data.global.get::<MainConfig>().unwrap();
+        let status = framework.data.global.get::<Status>().unwrap();
+
+        if !status.render_image
+            || !cfg!(any(feature = "halfblock", feature = "sixel"))
+            || !main_config.images.display()
+            || status.popup_opened
+        {
+            return self.lazy_scroll;
+        }
+
+        #[cfg(any(feature = "sixel", feature = "halfblock"))]
+        if let Some(item) = &self.item {
+            let thumbnail_path = home_dir()
+                .unwrap()
+                .join(".cache/youtube-tui/thumbnails/")
+                .join(item.thumbnail_id());
+            if thumbnail_path.exists() {
+                let config = Config {
+                    width: Some((area.width / 2) as u32),
+                    x: area.x,
+                    y: area.y as i16,
+                    ..Default::default()
+                };
+
+                if let Ok((_, height)) = print_from_file(thumbnail_path, &config) {
+                    // leave a line between the thumbnail and the text
+                    self.lazy_scroll = height as u16 + 1;
+                    framework
+                        .data
+                        .state
+                        .get_mut::<Tasks>()
+                        .unwrap()
+                        .priority
+                        .push(Task::LazyRendered);
+                }
+            }
+        }
+
+        #[cfg(not(any(feature = "sixel", feature = "halfblock")))]
+        let _ = area;
+
+        self.lazy_scroll
+    }
+}
@@ -37,7 +37,11 @@ impl PageButton {
                 r#type: ChannelDisplayPageType::Playlists,
             }),
             Self::History => Page::MainMenu(MainMenuPage::History),
-            Self::Feed => Page::Feed,
+            // stays in the same group if already in the feed
+            Self::Feed => match current_page {
+                Page::Feed(group) => Page::Feed(group.clone()),
+                _ => Page::Feed(None),
+            },
             Self::Library => Page::MainMenu(MainMenuPage::Library),
         }
     }
@@ -105,7 +109,18 @@ impl FrameworkItem for PageButton {
                 appearance.colors.outline
             }))
             .borders(Borders::ALL);
-        let paragraph = Paragraph::new(self.to_string())
+        let new_videos = framework
+            .data
+            .global
+            .get::<SyncStatus>()
+            .unwrap()
+            .new_videos;
+        let text = if *self == Self::Feed && new_videos != 0 {
+            format!("{} ({new_videos} new)", self.to_string())
+        } else {
+            self.to_string()
+        };
+        let paragraph = Paragraph::new(text)
             .block(block)
             .alignment(Alignment::Center);
 
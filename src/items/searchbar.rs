@@ -11,11 +11,14 @@ use crate::global::functions::{next_word, previous_word, remove_word};
 
 use crossterm::event::KeyCode;
 use ratatui::{
-    layout::Alignment,
+    layout::{Alignment, Rect},
     style::Style,
-    widgets::{Block, Borders},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use tui_additions::{
+    framework::{FrameworkClean, FrameworkItem},
+    widgets::TextField,
 };
-use tui_additions::{framework::FrameworkItem, widgets::TextField};
 
 /// the search bar item
 #[derive(Clone, Default)]
@@ -23,6 +26,105 @@ pub struct SearchBar {
     pub text_field: TextField,
     pub history_index: Option<usize>,
     pub custom_value_cache: String,
+    /// if the suggestions dropdown is shown, it is shown once the text is edited
+    pub dropdown: bool,
+    /// index of the highlighted entry in the dropdown
+    pub suggestion_index: Option<usize>,
+    /// if `popup_opened` is set by the dropdown, so that popups opened by other items (e.g. the
+    /// search filters) are not closed with it
+    pub dropdown_popup: bool,
+}
+
+impl SearchBar {
+    /// entries of the dropdown, empty if it is not shown
+    fn dropdown_entries(&self, framework: &FrameworkClean) -> Vec<String> {
+        if !self.dropdown || self.history_index.is_some() {
+            return Vec::new();
+        }
+
+        framework
+            .data
+            .global
+            .get::<SearchSuggestions>()
+            .unwrap()
+            .entries(
+                &self.text_field.content,
+                framework.data.global.get::<SearchHistory>().unwrap(),
+                framework
+                    .data
+                    .global
+                    .get::<MainConfig>()
+                    .unwrap()
+                    .limits
+                    .search_suggestions,
+            )
+    }
+
+    /// shows the dropdown and fetches suggestions for the new text, if the text is edited
+    fn edited(&mut self, framework: &FrameworkClean, previous: &str) {
+        if self.text_field.content == previous || self.history_index.is_some() {
+            return;
+        }
+
+        self.dropdown = true;
+        self.suggestion_index = None;
+
+        // matching searches from history are still shown in offline mode
+        if framework
+            .data
+            .global
+            .get::<MainConfig>()
+            .unwrap()
+            .limits
+            .search_suggestions
+            == 0
+            || framework
+                .data
+                .global
+                .get::<ResponseCache>()
+                .unwrap()
+                .offline
+        {
+            return;
+        }
+
+        framework
+            .data
+            .global
+            .get::<SearchSuggestions>()
+            .unwrap()
+            .request(
+                framework
+                    .data
+                    .global
+                    .get::<Instances>()
+                    .unwrap()
+                    .current_url(),
+                &self.text_field.content,
+            );
+    }
+
+    /// hide the dropdown, images covered by it are rendered again
+    fn close_dropdown(&mut self, framework: &mut FrameworkClean) {
+        self.dropdown = false;
+        self.suggestion_index = None;
+        self.set_dropdown_popup(framework, false);
+    }
+
+    /// sets `popup_opened` when the dropdown is shown, and only clears it when hiding the dropdown
+    /// if it is set by the dropdown
+    fn set_dropdown_popup(&mut self, framework: &mut FrameworkClean, opened: bool) {
+        let status = framework.data.global.get_mut::<Status>().unwrap();
+        if opened && !status.popup_opened {
+            status.popup_opened = true;
+            self.dropdown_popup = true;
+        } else if !opened && self.dropdown_popup {
+            // the search filters could have been opened over the dropdown
+            status.popup_opened = status.search_filter_opened;
+            status.render_image = true;
+            self.dropdown_popup = false;
+        }
+    }
 }
 
 impl FrameworkItem for SearchBar {
@@ -35,10 +137,64 @@ impl FrameworkItem for SearchBar {
         popup_render: bool,
         info: tui_additions::framework::ItemInfo,
     ) {
+        let entries = if info.selected {
+            self.dropdown_entries(framework)
+        } else {
+            Vec::new()
+        };
+
         if popup_render {
+            if entries.is_empty() {
+                return;
+            }
+
+            // the dropdown is right below the search bar, cut off at the bottom of the screen
+            let area = Rect {
+                y: area.bottom(),
+                height: (entries.len() as u16 + 2)
+                    .min(frame.size().bottom().saturating_sub(area.bottom())),
+                ..area
+            };
+            if area.height < 3 {
+                return;
+            }
+
+            frame.render_widget(Clear, area);
+
+            let appearance = framework.data.global.get::<AppearanceConfig>().unwrap();
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_type(appearance.borders)
+                .border_style(Style::default().fg(appearance.colors.outline_selected));
+            let inner = block.inner(area);
+            frame.render_widget(block, area);
+
+            for (index, (entry, y)) in entries.iter().zip(inner.top()..inner.bottom()).enumerate() {
+                let paragraph = Paragraph::new(entry.as_str()).style(Style::default().fg(
+                    if self.suggestion_index == Some(index) {
+                        appearance.colors.text_special
+                    } else {
+                        appearance.colors.text
+                    },
+                ));
+                frame.render_widget(
+                    paragraph,
+                    Rect {
+                        y,
+                        height: 1,
+                        ..inner
+                    },
+                );
+            }
+
             return;
         }
 
+        // images under the dropdown are not rendered while it is shown
+        if info.selected {
+            self.set_dropdown_popup(framework, !entries.is_empty());
+        }
+
         let appearance = framework.data.global.get::<AppearanceConfig>().unwrap();
 
         let block = Block::default()
@@ -83,6 +239,8 @@ impl FrameworkItem for SearchBar {
         key: crossterm::event::KeyEvent,
         _info: tui_additions::framework::ItemInfo,
     ) -> Result<(), Box<dyn Error>> {
+        let previous = self.text_field.content.clone();
+        let entries = self.dropdown_entries(framework);
         let mut render = true;
         match framework
             .data
@@ -113,6 +271,16 @@ impl FrameworkItem for SearchBar {
                 self.text_field.scroll = 0;
                 self.text_field.cursor = 0;
             }
+            // arrow keys move around the dropdown when it is shown
+            Some(KeyAction::MoveDown) if !entries.is_empty() => {
+                self.suggestion_index = Some(
+                    self.suggestion_index
+                        .map_or(0, |index| (index + 1).min(entries.len() - 1)),
+                )
+            }
+            Some(KeyAction::MoveUp) if self.suggestion_index.is_some() => {
+                self.suggestion_index = self.suggestion_index.and_then(|index| index.checked_sub(1))
+            }
             Some(KeyAction::First | KeyAction::MoveUp) => self.text_field.cursor = 0,
             Some(KeyAction::End | KeyAction::MoveDown) => {
                 self.text_field.cursor = self.text_field.content.len()
@@ -149,6 +317,7 @@ impl FrameworkItem for SearchBar {
         }
 
         if render {
+            self.edited(framework, &previous);
             framework
                 .data
                 .state
@@ -168,6 +337,13 @@ impl FrameworkItem for SearchBar {
             KeyCode::Right => self.text_field.right().is_ok(),
             KeyCode::Left => self.text_field.left().is_ok(),
             KeyCode::Enter => {
+                // search for the highlighted entry in the dropdown instead
+                if let Some(entry) = self.suggestion_index.and_then(|index| entries.get(index)) {
+                    self.text_field.content = entry.clone();
+                    let _ = self.text_field.last();
+                }
+                self.close_dropdown(framework);
+
                 if self.text_field.content.is_empty() {
                     *framework.data.global.get_mut::<Message>().unwrap() =
                         Message::Message(String::from("Search string must not be empty"));
@@ -192,11 +368,14 @@ impl FrameworkItem for SearchBar {
                 let search = search.clone();
                 let tasks = framework.data.state.get_mut::<Tasks>().unwrap();
                 tasks.priority.push(Task::LoadPage(Page::Search(search)));
-                false
+                // the dropdown stays closed after searching
+                return Ok(());
             }
             _ => false,
         };
 
+        self.edited(framework, &previous);
+
         // only re-render screen if updated
         if updated {
             let tasks = framework.data.state.get_mut::<Tasks>().unwrap();
@@ -235,4 +414,9 @@ impl FrameworkItem for SearchBar {
     fn selectable(&self) -> bool {
         true
     }
+
+    fn deselect(&mut self, framework: &mut FrameworkClean) -> bool {
+        self.close_dropdown(framework);
+        true
+    }
 }
@@ -5,7 +5,6 @@ use crate::{
     config::*,
     global::{functions::*, structs::*, traits::Collection},
 };
-use home::home_dir;
 use ratatui::{
     layout::{Constraint, Rect},
     style::Style,
@@ -44,7 +43,7 @@ pub struct SingleItem {
 #[derive(Clone)]
 pub enum SingleItemType {
     None,
-    Video(SingleVideoItem),
+    Video(Box<SingleVideoItem>),
     Playlist(Box<SinglePlaylistItem>),
 }
 
@@ -52,6 +51,37 @@ pub enum SingleItemType {
 pub struct SingleVideoItem {
     pub textlist: TextList,
     pub commands: Vec<(String, String)>,
+    pub chapters_view: TextList,
+    pub chapters: Vec<Chapter>,
+    pub comments_view: TextList,
+    /// `None` if comments have not been loaded
+    pub comments: Option<Comments>,
+    /// what each line in `comments_view` does
+    pub comments_entries: Vec<CommentsEntry>,
+    pub transcript_view: TextList,
+    /// revision of `Captions` that `transcript_view` is showing
+    pub transcript_revision: Option<usize>,
+    pub view: VideoView,
+}
+
+/// the textlists that can be displayed in a video page, `%switch-view%` cycles through them
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum VideoView {
+    Commands,
+    Chapters,
+    Comments,
+    Transcript,
+}
+
+impl VideoView {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Commands => Self::Chapters,
+            Self::Chapters => Self::Comments,
+            Self::Comments => Self::Transcript,
+            Self::Transcript => Self::Commands,
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -64,7 +94,12 @@ pub struct SinglePlaylistItem {
 }
 
 impl SingleVideoItem {
-    pub fn new(commands: &CommandsConfig, mainconfig: &MainConfig, id: &str) -> Self {
+    pub fn new(
+        commands: &CommandsConfig,
+        mainconfig: &MainConfig,
+        id: &str,
+        chapters: &[Chapter],
+    ) -> Self {
         let saved = find_library_item(id, mainconfig).is_some();
         if saved {
             Self::new_with_map(
@@ -74,6 +109,7 @@ impl SingleVideoItem {
                     .into_iter()
                     .map(|(display, command)| (display, command))
                     .collect(),
+                chapters,
             )
         } else {
             Self::new_with_map(
@@ -83,11 +119,12 @@ impl SingleVideoItem {
                     .into_iter()
                     .map(|(display, command)| (display, command))
                     .collect(),
+                chapters,
             )
         }
     }
 
-    pub fn new_with_map(commands: Vec<(String, String)>) -> Self {
+    pub fn new_with_map(commands: Vec<(String, String)>, chapters: &[Chapter]) -> Self {
         Self {
             textlist: TextList::default()
                 .items(
@@ -98,38 +135,274 @@ impl SingleVideoItem {
                 )
                 .unwrap(),
             commands,
+            chapters_view: TextList::default()
+                .items(&{
+                    let mut items = vec![String::from("Switch view")];
+                    if chapters.is_empty() {
+                        items.push(String::from("No chapters found"));
+                    }
+                    items.extend(chapters.iter().map(Chapter::to_string));
+                    items
+                })
+                .unwrap(),
+            chapters: chapters.to_vec(),
+            comments_view: TextList::default(),
+            comments: None,
+            comments_entries: Vec::new(),
+            transcript_view: TextList::default(),
+            transcript_revision: None,
+            view: VideoView::Commands,
         }
     }
 
-    /// find all occurances of ${provider}
+    /// the textlist that is currently displayed
+    pub fn current_view(&mut self) -> &mut TextList {
+        match self.view {
+            VideoView::Commands => &mut self.textlist,
+            VideoView::Chapters => &mut self.chapters_view,
+            VideoView::Comments => &mut self.comments_view,
+            VideoView::Transcript => &mut self.transcript_view,
+        }
+    }
+
+    /// go to the next view, comments are loaded (from cache if avaliable) the first time the
+    /// comments view is opened
+    pub fn switch_view(&mut self, framework: &mut FrameworkClean, id: &str) {
+        self.view = self.view.next();
+        let mut error = None;
+
+        if self.view == VideoView::Comments && self.comments.is_none() {
+            match Comments::load(id, |sort, continuation| {
+                request_with_failover(&mut framework.data, |instance| {
+                    CommentsPage::fetch(instance, id, sort, continuation)
+                })
+            }) {
+                Ok(comments) => {
+                    let _ = comments.save(id);
+                    self.comments = Some(comments);
+                    self.update_comments_view();
+                }
+                Err(e) => {
+                    // skips to the transcript view, which still has to be loaded
+                    self.view = self.view.next();
+                    error = Some(format!("Failed to load comments: {e}"));
+                }
+            }
+        }
+
+        if self.view == VideoView::Transcript {
+            if let Err(e) = self.load_transcript(framework, id) {
+                self.view = self.view.next();
+                error = Some(match error {
+                    Some(error) => format!("{error}, failed to load captions: {e}"),
+                    None => format!("Failed to load captions: {e}"),
+                });
+            }
+        }
+
+        *framework.data.global.get_mut::<Message>().unwrap() = match error {
+            Some(error) => Message::Error(error),
+            None => Message::Success(String::from("Switched view")),
+        };
+    }
+
+    /// regenerate lines in the comments view, keeping the cursor position
+    pub fn update_comments_view(&mut self) {
+        let comments = match &self.comments {
+            Some(comments) => comments,
+            None => return,
+        };
+
+        let (lines, entries): (Vec<_>, Vec<_>) = comments.entries().into_iter().unzip();
+        let selected = self.comments_view.selected;
+        let _ = self.comments_view.set_items(&lines);
+        self.comments_view.selected = selected.min(lines.len() - 1);
+        self.comments_entries = entries;
+    }
+
+    /// fetch caption tracks of the video and load the first track, if they are not already loaded
+    fn load_transcript(
+        &mut self,
+        framework: &mut FrameworkClean,
+        id: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let instance = framework
+            .data
+            .global
+            .get::<MainConfig>()
+            .unwrap()
+            .invidious_instance
+            .clone();
+        let captions = framework.data.global.get_mut::<Captions>().unwrap();
+        if captions.id.as_deref() != Some(id) {
+            captions.fetch_tracks(&instance, id)?;
+        }
+        if captions.track.is_none() && !captions.tracks.is_empty() {
+            captions.load_track(&instance, 0)?;
+        }
+
+        self.sync_transcript(captions);
+        Ok(())
+    }
+
+    /// update the transcript view if another track is loaded, and move the cursor to search
+    /// results
+    pub fn sync_transcript(&mut self, captions: &mut Captions) {
+        if self.transcript_revision != Some(captions.revision) {
+            let mut lines = vec![String::from("Switch view")];
+            match captions.track {
+                Some(track) => lines.push(format!(
+                    "Captions: {} ({}/{})",
+                    captions.tracks[track].label,
+                    track + 1,
+                    captions.tracks.len()
+                )),
+                None => lines.push(String::from("No captions avaliable")),
+            }
+            lines.extend(captions.cues.iter().map(Cue::to_string));
+
+            let _ = self.transcript_view.set_items(&lines);
+            let _ = self.transcript_view.first();
+            self.transcript_revision = Some(captions.revision);
+        }
+
+        // the first 2 lines are not cues
+        if let Some(jump) = captions.jump.take() {
+            let _ = self.transcript_view.first();
+            for _ in 0..jump + 2 {
+                let _ = self.transcript_view.down();
+            }
+        }
+    }
+
+    /// handle selecting a line in the transcript view
+    pub fn select_transcript(&mut self, framework: &mut FrameworkClean, id: &str) {
+        let selected = self.transcript_view.selected;
+        if selected == 0 {
+            self.switch_view(framework, id);
+            return;
+        }
+
+        let instance = framework
+            .data
+            .global
+            .get::<MainConfig>()
+            .unwrap()
+            .invidious_instance
+            .clone();
+        let captions = framework.data.global.get_mut::<Captions>().unwrap();
+
+        // cycle through caption tracks
+        if selected == 1 {
+            let track = match captions.track {
+                Some(track) => (track + 1) % captions.tracks.len(),
+                None => return,
+            };
+            match captions.load_track(&instance, track) {
+                Ok(()) => self.sync_transcript(captions),
+                Err(e) => {
+                    *framework.data.global.get_mut::<Message>().unwrap() =
+                        Message::Error(format!("Failed to load captions: {e}"))
+                }
+            }
+            return;
+        }
+
+        captions.cursor = selected - 2;
+        let start = captions.cues[selected - 2].start;
+        framework
+            .data
+            .state
+            .get_mut::<Tasks>()
+            .unwrap()
+            .priority
+            .push(Task::Command(format!("mpv playfrom {start}")));
+    }
+
+    /// handle selecting a line in the comments view
+    pub fn select_comment(&mut self, framework: &mut FrameworkClean, id: &str) {
+        let entry = self.comments_entries[self.comments_view.selected];
+        if entry == CommentsEntry::SwitchView {
+            self.switch_view(framework, id);
+            return;
+        }
+
+        let comments = match &mut self.comments {
+            Some(comments) => comments,
+            None => return,
+        };
+        let fetch = |sort, continuation: Option<&str>| {
+            request_with_failover(&mut framework.data, |instance| {
+                CommentsPage::fetch(instance, id, sort, continuation)
+            })
+        };
+
+        let res = match entry {
+            CommentsEntry::Sort => comments.toggle_sort(fetch),
+            CommentsEntry::Comment(index, None) => comments.toggle_replies(fetch, index),
+            CommentsEntry::MoreReplies(index) => comments.load_replies(fetch, index),
+            CommentsEntry::LoadMore => comments.load_more(fetch),
+            _ => return,
+        };
+
+        match res {
+            Ok(()) => {
+                let _ = comments.save(id);
+                self.update_comments_view();
+            }
+            Err(e) => {
+                *framework.data.global.get_mut::<Message>().unwrap() =
+                    Message::Error(format!("Failed to load comments: {e}"))
+            }
+        }
+    }
+
+    /// the hovered chapter in chapters view, `None` if hovering on "Switch view"
+    pub fn hovered_chapter(&self) -> Option<(usize, &Chapter)> {
+        let index = self.chapters_view.selected.checked_sub(1)?;
+        Some((index, self.chapters.get(index)?))
+    }
+
+    /// find all occurances of ${provider}, and commands that are marked in offline mode
     pub fn update_provider(&mut self) -> Vec<usize> {
         self.commands
             .iter()
             .enumerate()
-            .filter(|(_index, (display, _command))| display.contains("${provider}"))
+            .filter(|(_index, (display, command))| {
+                display.contains("${provider}") || Self::network_only(command)
+            })
             .map(|(index, _)| index)
             .collect()
     }
 
+    /// commands that cannot work without a connection, they are marked and cannot be selected in
+    /// offline mode
+    pub fn network_only(command: &str) -> bool {
+        command.contains("${embed-url}")
+            || command.contains("${url}")
+            || command.split(";;").any(|single| {
+                matches!(
+                    single.split_ascii_whitespace().next(),
+                    Some("download" | "sync" | "syncall" | "cache")
+                )
+            })
+    }
+
     pub fn update_appearance(
         &mut self,
         appearance: &AppearanceConfig,
         iteminfo: &tui_additions::framework::ItemInfo,
     ) {
-        self.textlist.set_border_type(appearance.borders);
-        self.textlist
-            .set_style(Style::default().fg(appearance.colors.text));
+        let textlist = self.current_view();
+        textlist.set_border_type(appearance.borders);
+        textlist.set_style(Style::default().fg(appearance.colors.text));
 
         if iteminfo.selected {
-            self.textlist
-                .set_cursor_style(Style::default().fg(appearance.colors.outline_hover));
-            self.textlist
-                .set_selected_style(Style::default().fg(appearance.colors.text_special));
+            textlist.set_cursor_style(Style::default().fg(appearance.colors.outline_hover));
+            textlist.set_selected_style(Style::default().fg(appearance.colors.text_special));
         } else {
-            self.textlist
-                .set_cursor_style(Style::default().fg(appearance.colors.outline_secondary));
-            self.textlist
-                .set_selected_style(Style::default().fg(appearance.colors.text_secondary));
+            textlist.set_cursor_style(Style::default().fg(appearance.colors.outline_secondary));
+            textlist.set_selected_style(Style::default().fg(appearance.colors.text_secondary));
         }
     }
 
@@ -436,6 +709,17 @@ impl SingleItem {
                     None => String::from("not avaliable"),
                 },
             )]
+        } else if let SingleItemType::Video(singlevideoitem) = &self.r#type {
+            // play from the first chapter (start of the video) if not hovering any chapters
+            let (index, start) = singlevideoitem
+                .hovered_chapter()
+                .map(|(index, chapter)| (index, chapter.start))
+                .unwrap_or_default();
+            vec![
+                (String::from("hover-url"), String::from("not avaliable")),
+                (String::from("chapter"), (index + 1).to_string()),
+                (String::from("start-time"), start.to_string()),
+            ]
         } else {
             vec![(String::from("hover-url"), String::from("not avaliable"))]
         }
@@ -499,18 +783,72 @@ impl SingleItem {
     ) {
         match &mut self.r#type {
             SingleItemType::Video(singlevideoitem) => {
-                let command_string = singlevideoitem.commands[singlevideoitem.textlist.selected]
-                    .1
-                    .clone();
-
-                // check if the command starts with an ':' which case should be captured
-                framework
-                    .data
-                    .state
-                    .get_mut::<Tasks>()
+                let id = self
+                    .item
+                    .as_ref()
                     .unwrap()
-                    .priority
-                    .push(Task::Command(apply_envs(command_string)));
+                    .id()
+                    .unwrap_or_default()
+                    .to_string();
+                match singlevideoitem.view {
+                    VideoView::Commands => {
+                        let command_string = singlevideoitem.commands
+                            [singlevideoitem.textlist.selected]
+                            .1
+                            .clone();
+
+                        // checks for special cases
+                        match command_string.as_str() {
+                            "%switch-view%" => singlevideoitem.switch_view(framework, &id),
+                            _ if SingleVideoItem::network_only(&command_string)
+                                && framework
+                                    .data
+                                    .global
+                                    .get::<ResponseCache>()
+                                    .unwrap()
+                                    .offline =>
+                            {
+                                *framework.data.global.get_mut::<Message>().unwrap() =
+                                    Message::Error(String::from(
+                                        "Not available in offline mode, run `offline off` first",
+                                    ));
+                                framework
+                                    .data
+                                    .state
+                                    .get_mut::<Tasks>()
+                                    .unwrap()
+                                    .priority
+                                    .push(Task::RenderAll);
+                            }
+                            _ => {
+                                // check if the command starts with an ':' which case should be captured
+                                framework
+                                    .data
+                                    .state
+                                    .get_mut::<Tasks>()
+                                    .unwrap()
+                                    .priority
+                                    .push(Task::Command(apply_envs(command_string)));
+                            }
+                        }
+                    }
+                    // selecting a chapter plays the video from there
+                    VideoView::Chapters => match singlevideoitem.hovered_chapter() {
+                        Some((index, _)) => framework
+                            .data
+                            .state
+                            .get_mut::<Tasks>()
+                            .unwrap()
+                            .priority
+                            .push(Task::Command(format!("mpv chapter {}", index + 1))),
+                        None if singlevideoitem.chapters_view.selected == 0 => {
+                            singlevideoitem.switch_view(framework, &id)
+                        }
+                        None => {}
+                    },
+                    VideoView::Comments => singlevideoitem.select_comment(framework, &id),
+                    VideoView::Transcript => singlevideoitem.select_transcript(framework, &id),
+                }
             }
             SingleItemType::Playlist(singleplaylistitem) => {
                 let command_string = singleplaylistitem.commands
@@ -601,23 +939,42 @@ impl FrameworkItem for SingleItem {
             SingleItemType::Video(typeinfo) => {
                 // 2 by 1 grid, item info in the first cell and textlist at the second
                 if status.provider_updated {
+                    let offline = framework
+                        .data
+                        .global
+                        .get::<ResponseCache>()
+                        .unwrap()
+                        .offline;
                     typeinfo.update_provider().into_iter().for_each(|index| {
-                        typeinfo.textlist.items[index] = typeinfo.commands[index].0.clone().replace(
-                            "${provider}",
-                            framework
-                                .data
-                                .global
-                                .get::<Status>()
-                                .unwrap()
-                                .provider
-                                .as_str(),
+                        let (display, command) = &typeinfo.commands[index];
+                        typeinfo.textlist.items[index] = format!(
+                            "{}{}",
+                            if offline && SingleVideoItem::network_only(command) {
+                                "[Online only] "
+                            } else {
+                                ""
+                            },
+                            display.replace(
+                                "${provider}",
+                                framework
+                                    .data
+                                    .global
+                                    .get::<Status>()
+                                    .unwrap()
+                                    .provider
+                                    .as_str(),
+                            )
                         )
                     });
                 }
                 self.iteminfo
                     .render(frame, framework, chunks[0], popup_render, info);
-                typeinfo.textlist.set_height(chunks[1].height);
-                frame.render_widget(typeinfo.textlist.clone(), chunks[1]);
+                if typeinfo.view == VideoView::Transcript {
+                    typeinfo.sync_transcript(framework.data.global.get_mut::<Captions>().unwrap());
+                }
+                let textlist = typeinfo.current_view();
+                textlist.set_height(chunks[1].height);
+                frame.render_widget(textlist.clone(), chunks[1]);
             }
             SingleItemType::Playlist(typeinfo) => {
                 // 3 by 1 grid if hovering a video inside the playlist
@@ -684,41 +1041,30 @@ impl FrameworkItem for SingleItem {
         // gets the item that it needs to load from `data.state.Page`
         let (item, r#type) = match r#type {
             SingleItemPage::Video(id) => {
-                let home_dir = home_dir().unwrap();
-                let path = home_dir.join(format!(".cache/youtube-tui/info/{id}.json"));
-
-                let video = if path.exists() {
-                    serde_json::from_str(&fs::read_to_string(path)?)?
-                } else {
-                    load_video(
-                        &framework.data.global.get::<InvidiousClient>().unwrap().0,
-                        id,
-                        mainconfig,
-                    )?
-                };
-                (
-                    video,
-                    SingleItemType::Video(SingleVideoItem::new(
-                        framework.data.global.get::<CommandsConfig>().unwrap(),
-                        mainconfig,
-                        id,
-                    )),
-                )
+                let video = load_video(
+                    &framework.data.global.get::<InvidiousClient>().unwrap().0,
+                    framework.data.global.get::<ResponseCache>().unwrap(),
+                    id,
+                    mainconfig,
+                )?;
+                let r#type = SingleItemType::Video(Box::new(SingleVideoItem::new(
+                    framework.data.global.get::<CommandsConfig>().unwrap(),
+                    mainconfig,
+                    id,
+                    match &video {
+                        Item::FullVideo(FullVideoItem { chapters, .. }) => chapters,
+                        _ => &[],
+                    },
+                )));
+                (video, r#type)
             }
             SingleItemPage::Playlist(id) => {
-                let path = home_dir()
-                    .unwrap()
-                    .join(format!(".cache/youtube-tui/info/{id}.json"));
-
-                let playlist = if path.exists() {
-                    serde_json::from_str(&fs::read_to_string(path)?)?
-                } else {
-                    load_playlist(
-                        &framework.data.global.get::<InvidiousClient>().unwrap().0,
-                        id,
-                        mainconfig,
-                    )?
-                };
+                let playlist = load_playlist(
+                    &framework.data.global.get::<InvidiousClient>().unwrap().0,
+                    framework.data.global.get::<ResponseCache>().unwrap(),
+                    id,
+                    mainconfig,
+                )?;
                 let r#type = SingleItemType::Playlist(
                     SinglePlaylistItem::new(
                         framework.data.global.get::<CommandsConfig>().unwrap(),
@@ -797,18 +1143,42 @@ impl FrameworkItem for SingleItem {
         };
 
         let updated = match &mut self.r#type {
-            SingleItemType::Video(singlevideoitem) => match action {
-                // move the cursor in the textlist, only update the screen if it is changed
-                KeyAction::MoveUp => singlevideoitem.textlist.up().is_ok(),
-                KeyAction::MoveDown => singlevideoitem.textlist.down().is_ok(),
-                KeyAction::MoveLeft | KeyAction::First => singlevideoitem.textlist.first().is_ok(),
-                KeyAction::MoveRight | KeyAction::End => singlevideoitem.textlist.last().is_ok(),
-                KeyAction::Select => {
-                    self.select_at_cursor(framework);
-                    return Ok(());
+            SingleItemType::Video(singlevideoitem) => {
+                let view = singlevideoitem.view;
+                let textlist = singlevideoitem.current_view();
+                let updated = match action {
+                    // move the cursor in the textlist, only update the screen if it is changed
+                    KeyAction::MoveUp => textlist.up().is_ok(),
+                    KeyAction::MoveDown => textlist.down().is_ok(),
+                    KeyAction::MoveLeft | KeyAction::First => textlist.first().is_ok(),
+                    KeyAction::MoveRight | KeyAction::End => textlist.last().is_ok(),
+                    KeyAction::Select => {
+                        self.select_at_cursor(framework);
+                        return Ok(());
+                    }
+                    _ => false,
+                };
+
+                // searching in captions starts from the hovered line
+                if updated && view == VideoView::Transcript {
+                    framework.data.global.get_mut::<Captions>().unwrap().cursor =
+                        singlevideoitem.transcript_view.selected.saturating_sub(2);
+                }
+
+                // hovering on a chapter changes `${chapter}` and `${start-time}`
+                if updated && view == VideoView::Chapters {
+                    set_envs(
+                        self.infalte_item_update(
+                            framework.data.global.get::<MainConfig>().unwrap(),
+                            framework.data.global.get::<Status>().unwrap(),
+                        )
+                        .into_iter(),
+                        &mut framework.data.state.get_mut::<StateEnvs>().unwrap().0,
+                    );
                 }
-                _ => false,
-            },
+
+                updated
+            }
             SingleItemType::Playlist(singleplaylistitem) => {
                 // there are 2 possible states in a playlist item
                 // they are handelled separately
@@ -1000,7 +1370,7 @@ impl FrameworkItem for SingleItem {
         }
 
         let textlist = match &mut self.r#type {
-            SingleItemType::Video(SingleVideoItem { textlist, .. }) => textlist,
+            SingleItemType::Video(singlevideoitem) => singlevideoitem.current_view(),
             SingleItemType::Playlist(singleplaylistitem) => {
                 if singleplaylistitem.is_commands_view {
                     &mut singleplaylistitem.commands_view
@@ -76,16 +76,22 @@ impl VideoList {
         }
     }
 
-    fn update_items(&mut self, subscriptions: &Subscriptions, subselect: usize) {
+    fn update_items(&mut self, subscriptions: &Subscriptions, page: &Page, subselect: usize) {
         self.previous = subselect;
+        // only channels in the group are listed by channellist
+        let group = match page {
+            Page::Feed(group) => group.as_deref(),
+            _ => None,
+        };
+        let channels = subscriptions.in_group(group);
         if subselect == 0 {
             // if channellist is at index 0 (first item), then fetch all feeds
             self.channel_id = None;
-            self.items = subscriptions.get_all_videos();
-        } else if subselect <= subscriptions.0.len() {
+            self.items = subscriptions.get_all_videos(group);
+        } else if subselect <= channels.len() {
             // or else, only fetch the one channel
-            self.channel_id = Some(subscriptions.0[subselect - 1].channel.id.clone());
-            self.items = subscriptions.0[subselect - 1].videos.clone();
+            self.channel_id = Some(channels[subselect - 1].channel.id.clone());
+            self.items = channels[subselect - 1].videos.clone();
         } else {
             // no idea when will this be true, just here to prevent some errors.
             self.channel_id = None;
@@ -213,6 +219,7 @@ impl FrameworkItem for VideoList {
         // put the items into self.items
         self.update_items(
             framework.data.global.get::<Subscriptions>().unwrap(),
+            framework.data.state.get::<Page>().unwrap(),
             framework
                 .data
                 .global
@@ -264,6 +271,7 @@ impl FrameworkItem for VideoList {
         {
             self.update_items(
                 framework.data.global.get::<Subscriptions>().unwrap(),
+                framework.data.state.get::<Page>().unwrap(),
                 subselect.0,
             );
             framework
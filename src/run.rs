@@ -1,8 +1,11 @@
 use crossterm::event::{self, Event, MouseButton, MouseEventKind};
 use ratatui::{backend::CrosstermBackend, Terminal};
-#[cfg(feature = "mpv")]
-use std::time::{Duration, Instant};
-use std::{any::TypeId, error::Error, io::Stdout};
+use std::{
+    any::TypeId,
+    error::Error,
+    io::Stdout,
+    time::{Duration, Instant},
+};
 use tui_additions::framework::Framework;
 
 use crate::{
@@ -16,9 +19,8 @@ pub fn run(
     terminal: &mut Terminal<CrosstermBackend<Stdout>>,
     framework: &mut Framework,
 ) -> Result<(), Box<dyn Error>> {
-    #[cfg(feature = "mpv")]
     let tick_rate = Duration::from_secs(1);
-    #[cfg(feature = "mpv")]
+    let event_poll_rate = Duration::from_millis(100);
     let mut last_tick = Instant::now();
     loop {
         // repeat forever until all tasks are ran (and Tasks is cleared)
@@ -40,15 +42,154 @@ pub fn run(
             break;
         }
 
-        #[cfg(feature = "mpv")]
         if !event::poll(
             tick_rate
                 .checked_sub(last_tick.elapsed())
-                .unwrap_or_else(|| Duration::from_secs(0)),
+                .unwrap_or_else(|| Duration::from_secs(0))
+                .min(event_poll_rate),
         )? {
+            // events from mpv and downloaders are checked more often than the tick, so the TUI
+            // reacts to them immediately
+            #[cfg(feature = "mpv")]
+            while let Ok(event) = framework
+                .data
+                .global
+                .get::<MpvWrapper>()
+                .unwrap()
+                .events
+                .try_recv()
+            {
+                mpv_event(event, framework);
+            }
+
+            let events = framework
+                .data
+                .global
+                .get::<Downloads>()
+                .unwrap()
+                .events
+                .clone();
+            while let Ok(event) = events.lock().unwrap().try_recv() {
+                download_event(event, framework);
+            }
+
+            let events = framework
+                .data
+                .global
+                .get::<SyncStatus>()
+                .unwrap()
+                .events
+                .clone();
+            while let Ok(synced) = events.lock().unwrap().try_recv() {
+                sync_event(synced, framework);
+            }
+
+            let events = framework
+                .data
+                .global
+                .get::<Instances>()
+                .unwrap()
+                .events
+                .clone();
+            while let Ok(event) = events.lock().unwrap().try_recv() {
+                instance_event(event, framework);
+            }
+
+            let events = framework
+                .data
+                .global
+                .get::<SearchSuggestions>()
+                .unwrap()
+                .events
+                .clone();
+            while let Ok(fetched) = events.lock().unwrap().try_recv() {
+                framework
+                    .data
+                    .global
+                    .get_mut::<SearchSuggestions>()
+                    .unwrap()
+                    .latest = Some(fetched);
+                framework
+                    .data
+                    .state
+                    .get_mut::<Tasks>()
+                    .unwrap()
+                    .priority
+                    .push(Task::RenderAll);
+            }
+
+            if last_tick.elapsed() < tick_rate {
+                continue;
+            }
+
             // do tick changes
             last_tick = Instant::now();
+
+            // scheduled sync, the first one starts right after launching, or once back online
+            let offline = framework
+                .data
+                .global
+                .get::<ResponseCache>()
+                .unwrap()
+                .offline;
+            let interval = framework
+                .data
+                .global
+                .get::<MainConfig>()
+                .unwrap()
+                .syncing
+                .auto_sync_interval_secs;
+            let status = framework.data.global.get_mut::<SyncStatus>().unwrap();
+            if interval != 0
+                && !offline
+                && status
+                    .next_auto_sync
+                    .filter(|next_auto_sync| *next_auto_sync > last_tick)
+                    .is_none()
+            {
+                status.next_auto_sync = Some(last_tick + Duration::from_secs(interval));
+                start_sync(framework, true);
+            }
+
+            // instance health checks, the first one starts right after launching
+            let instances_config = &framework.data.global.get::<MainConfig>().unwrap().instances;
+            let (interval, timeout) = (
+                instances_config.check_interval_secs,
+                instances_config.check_timeout_secs,
+            );
+            let instances = framework.data.global.get_mut::<Instances>().unwrap();
+            if instances
+                .next_check
+                .is_some_and(|next_check| next_check <= last_tick)
+            {
+                instances.next_check =
+                    (interval != 0).then(|| last_tick + Duration::from_secs(interval));
+                instances.check_all(timeout);
+            }
+
+            // start failed downloads that are due to retry
+            if framework
+                .data
+                .global
+                .get::<Downloads>()
+                .unwrap()
+                .has_retries()
+            {
+                let mut downloads = framework.data.global.remove::<Downloads>().unwrap();
+                if downloads.start_queued(framework.data.global.get::<MainConfig>().unwrap()) {
+                    let _ = downloads.save();
+                }
+                framework.data.global.insert::<Downloads>(downloads);
+
+                // updates the retry countdown
+                if framework.data.state.get::<Page>().unwrap() == &Page::Downloads {
+                    TaskQueue::render(framework, terminal)?;
+                    continue;
+                }
+            }
+
             // TaskQueue::render_filter(framework, terminal, |item| (*item).type_id() == TypeId::of::<MessageBar>())?;
+            #[cfg(feature = "mpv")]
             if MessageBar::is_mpv_render(&framework.split_clean().0) {
                 TaskQueue::render(framework, terminal)?;
             }